@@ -1,7 +1,29 @@
+mod allpass;
+mod biquad;
 mod clock;
+mod convolver;
 mod dc_block;
 mod delay_line;
+mod envelope;
+mod lfo;
+mod noise;
+mod oversampled;
+mod quantizer;
+mod rms;
+mod stft;
+mod true_peak;
 
+pub use allpass::*;
+pub use biquad::*;
 pub use clock::*;
+pub use convolver::*;
 pub use dc_block::*;
 pub use delay_line::*;
+pub use envelope::*;
+pub use lfo::*;
+pub use noise::*;
+pub use oversampled::*;
+pub use quantizer::*;
+pub use rms::*;
+pub use stft::*;
+pub use true_peak::*;
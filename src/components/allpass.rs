@@ -0,0 +1,76 @@
+use core::f32::consts::PI;
+use libm::tanf;
+
+/// First-order allpass filter, i.e. a unity-gain filter with a phase shift of 90 degrees at the
+/// break frequency.
+///
+/// Unlike a delay-line (Schroeder) allpass, the phase response is swept smoothly by changing the
+/// break frequency, which makes it the building block for phasers.
+///
+/// ```
+/// use dspkit::components::FirstOrderAllPass;
+///
+/// let mut allpass = FirstOrderAllPass::const_default();
+/// allpass.set_frequency(1_000.0, 48_000);
+///
+/// // A DC signal passes through with unity gain.
+/// let out = (0..4096).fold(0.0, |_, _| allpass.tick(&1.0));
+/// assert!((out - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct FirstOrderAllPass {
+    coefficient: f32,
+    last_input: f32,
+    last_output: f32,
+}
+
+impl FirstOrderAllPass {
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        Self {
+            coefficient: 0.0,
+            last_input: 0.0,
+            last_output: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: &f32) -> f32 {
+        let out = self.coefficient * (input - self.last_output) + self.last_input;
+        self.last_input = *input;
+        self.last_output = out;
+        out
+    }
+
+    /// Set the break frequency in Hz, i.e. where the phase shift reaches 90 degrees.
+    #[inline(always)]
+    pub fn set_frequency(&mut self, frequency: f32, sample_rate: usize) {
+        self.coefficient = Self::coefficient(frequency, sample_rate);
+    }
+
+    /// Compute the filter coefficient for a break frequency in Hz. Useful when many filters share
+    /// the same break frequency.
+    #[inline(always)]
+    pub fn coefficient(frequency: f32, sample_rate: usize) -> f32 {
+        let nyquist = 0.5 * sample_rate as f32;
+        let frequency = frequency.clamp(1.0, 0.99 * nyquist);
+        let t = tanf(PI * frequency / sample_rate as f32);
+        (t - 1.0) / (t + 1.0)
+    }
+
+    /// Set the raw filter coefficient in the range `-1.0..1.0`.
+    pub fn set_coefficient(&mut self, coefficient: f32) {
+        self.coefficient = coefficient;
+    }
+
+    pub fn reset(&mut self) {
+        self.last_input = 0.0;
+        self.last_output = 0.0;
+    }
+}
+
+impl Default for FirstOrderAllPass {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
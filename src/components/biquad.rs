@@ -0,0 +1,114 @@
+use core::f32::consts::PI;
+use libm::{cosf, sinf};
+
+/// Second-order IIR filter in transposed direct form II.
+///
+/// Coefficients follow the [Audio EQ Cookbook](https://www.w3.org/TR/audio-eq-cookbook/) by Robert
+/// Bristow-Johnson. A default filter passes the signal through unchanged.
+///
+/// ```
+/// use dspkit::components::Biquad;
+///
+/// let mut hpf = Biquad::const_default();
+/// hpf.set_highpass(100.0, 0.707, 48_000);
+///
+/// // A DC signal is removed by the high-pass filter.
+/// let out = (0..48_000).fold(0.0, |_, _| hpf.tick(&1.0));
+/// assert!(out.abs() < 1e-3);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    /// Default const constructor, i.e. can be created at compile-time. The filter passes the
+    /// signal through unchanged until configured.
+    pub const fn const_default() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: &f32) -> f32 {
+        let out = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * out + self.z2;
+        self.z2 = self.b2 * input - self.a2 * out;
+        out
+    }
+
+    /// Pass the signal through unchanged.
+    pub fn set_bypass(&mut self) {
+        self.set_coefficients(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    }
+
+    /// Configure a second-order low-pass filter with a cutoff `frequency` in Hz.
+    pub fn set_lowpass(&mut self, frequency: f32, q: f32, sample_rate: usize) {
+        let (cos, alpha) = intermediates(frequency, q, sample_rate);
+        let b1 = 1.0 - cos;
+        self.set_coefficients(0.5 * b1, b1, 0.5 * b1, 1.0 + alpha, -2.0 * cos, 1.0 - alpha);
+    }
+
+    /// Configure a second-order high-pass filter with a cutoff `frequency` in Hz.
+    pub fn set_highpass(&mut self, frequency: f32, q: f32, sample_rate: usize) {
+        let (cos, alpha) = intermediates(frequency, q, sample_rate);
+        let b1 = -(1.0 + cos);
+        self.set_coefficients(
+            -0.5 * b1,
+            b1,
+            -0.5 * b1,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        );
+    }
+
+    /// Configure a band-pass filter with unity gain at the center `frequency` in Hz.
+    pub fn set_bandpass(&mut self, frequency: f32, q: f32, sample_rate: usize) {
+        let (cos, alpha) = intermediates(frequency, q, sample_rate);
+        self.set_coefficients(alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos, 1.0 - alpha);
+    }
+
+    /// Clear the filter state, keeping the coefficients.
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+
+    /// Set the coefficients, normalized by `a0`.
+    fn set_coefficients(&mut self, b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) {
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+    }
+}
+
+impl Default for Biquad {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+/// Compute `cos(w0)` and `alpha` for a frequency, clamped below the Nyquist frequency.
+#[inline(always)]
+fn intermediates(frequency: f32, q: f32, sample_rate: usize) -> (f32, f32) {
+    let nyquist = 0.5 * sample_rate as f32;
+    let frequency = frequency.clamp(1.0, 0.99 * nyquist);
+    let w0 = 2.0 * PI * frequency / sample_rate as f32;
+    (cosf(w0), sinf(w0) / (2.0 * q.max(0.01)))
+}
@@ -0,0 +1,255 @@
+use crate::AudioNode;
+use crate::fft::{Complex, RealFft};
+
+/// Uniformly partitioned convolution of a signal with an impulse response of up to `B * P`
+/// samples, split into `P` partitions of `B` samples.
+///
+/// Each block of `B` input samples is transformed once with an FFT of `2 * B` samples, and stored
+/// in a frequency-domain delay line of the `P` most recent input spectra. The output block is the
+/// sum of the products of the delayed input spectra with the spectra of the partitions, turned
+/// back into samples by overlap-save. The FFT is only computed once per block, so the cost per
+/// sample grows with `log(B)` and `P`, rather than with the length of the impulse response.
+///
+/// Processing by blocks delays the output by `B` samples. In the zero-latency mode, the first
+/// partition of the impulse response is instead convolved directly in the time domain, and the
+/// blocks only convolve the rest of the impulse response, which is late enough to hide the delay
+/// of the blocks. The direct head costs `B` multiplications per sample.
+///
+/// All of the storage is held in the struct, so `B` must be a power of two. Impulse responses
+/// longer than the capacity are truncated.
+///
+/// ```
+/// use dspkit::components::Convolver;
+///
+/// static CONVOLVER: Convolver<64, 8> = Convolver::const_default();
+///
+/// // A decaying impulse response spanning several partitions of 16 samples.
+/// static IMPULSE_RESPONSE: [f32; 200] = {
+///     let mut impulse_response = [0.0; 200];
+///     let mut n = 0;
+///     while n < 200 {
+///         let sign = if n % 3 == 0 { -1.0 } else { 1.0 };
+///         impulse_response[n] = sign / (n + 1) as f32;
+///         n += 1;
+///     }
+///     impulse_response
+/// };
+///
+/// let input = |n: usize| libm::sinf(0.3 * n as f32) + if n % 97 == 0 { 1.0 } else { 0.0 };
+/// let direct = |n: usize| -> f32 {
+///     let taps = IMPULSE_RESPONSE.iter().take(n + 1).enumerate();
+///     taps.map(|(k, h)| h * input(n - k)).sum()
+/// };
+///
+/// // The output matches a direct convolution, after the latency in both modes.
+/// for zero_latency in [false, true] {
+///     let mut convolver = Convolver::<16, 16>::const_default();
+///     convolver.set_impulse_response(&IMPULSE_RESPONSE);
+///     convolver.set_zero_latency(zero_latency);
+///
+///     let latency = convolver.latency();
+///     assert_eq!(latency, if zero_latency { 0 } else { 16 });
+///     for n in 0..600 {
+///         let output = convolver.tick(input(n));
+///         if n >= latency {
+///             assert!((output - direct(n - latency)).abs() < 1e-4);
+///         }
+///     }
+/// }
+/// ```
+pub struct Convolver<const B: usize, const P: usize> {
+    impulse_response: &'static [f32],
+    zero_latency: bool,
+    /// Spectra of the partitions which are convolved by blocks.
+    partitions: [[Complex; B]; P],
+    num_partitions: usize,
+    /// Spectra of the most recent input blocks, starting at `delay_index`.
+    delay: [[Complex; B]; P],
+    delay_index: usize,
+    /// Previous and current blocks of input.
+    input: [[f32; B]; 2],
+    output: [f32; B],
+    position: usize,
+    /// First partition of the impulse response, convolved directly in the zero-latency mode.
+    head: [f32; B],
+    head_length: usize,
+    history: [f32; B],
+    fft: RealFft<B>,
+    scratch: [Complex; B],
+}
+
+impl<const B: usize, const P: usize> Convolver<B, P> {
+    /// Default const constructor, i.e. can be created at compile-time. The impulse response is
+    /// empty, so the output is silent until [`Convolver::set_impulse_response`] is called.
+    pub const fn const_default() -> Self {
+        const {
+            assert!(
+                B.is_power_of_two() && B >= 2,
+                "block size must be a power of two of at least 2"
+            );
+            assert!(P >= 1, "a convolver requires at least one partition");
+        }
+
+        Self {
+            impulse_response: &[],
+            zero_latency: false,
+            partitions: [[Complex::ZERO; B]; P],
+            num_partitions: 0,
+            delay: [[Complex::ZERO; B]; P],
+            delay_index: 0,
+            input: [[0.0; B]; 2],
+            output: [0.0; B],
+            position: 0,
+            head: [0.0; B],
+            head_length: 0,
+            history: [0.0; B],
+            fft: RealFft::new(),
+            scratch: [Complex::ZERO; B],
+        }
+    }
+
+    /// Process a single sample.
+    #[inline]
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let position = self.position;
+        self.input[1][position] = input;
+        let mut output = self.output[position];
+
+        if self.head_length > 0 {
+            self.history[position] = input;
+            // The history is a ring buffer with the newest sample at the position, so reading
+            // backwards from the position yields the samples from newest to oldest.
+            let (newer, older) = self.history.split_at(position + 1);
+            output += newer
+                .iter()
+                .rev()
+                .chain(older.iter().rev())
+                .zip(self.head[..self.head_length].iter())
+                .map(|(x, h)| x * h)
+                .sum::<f32>();
+        }
+
+        self.position += 1;
+        if self.position == B {
+            self.position = 0;
+            self.process_block();
+        }
+
+        output
+    }
+
+    /// Convolve the last block of input with the partitions.
+    fn process_block(&mut self) {
+        if self.num_partitions == 0 {
+            self.input[0] = self.input[1];
+            return;
+        }
+
+        // Transform the previous and current blocks into the newest slot of the delay line.
+        self.delay_index = (self.delay_index + P - 1) % P;
+        let spectrum = &mut self.delay[self.delay_index];
+        for (n, value) in spectrum.iter_mut().enumerate() {
+            let samples = &self.input[2 * n / B];
+            let offset = (2 * n) % B;
+            *value = Complex::new(samples[offset], samples[offset + 1]);
+        }
+        self.fft.forward(spectrum);
+        self.input[0] = self.input[1];
+
+        self.scratch = [Complex::ZERO; B];
+        for (p, partition) in self.partitions[..self.num_partitions].iter().enumerate() {
+            let spectrum = &self.delay[(self.delay_index + p) % P];
+            // The DC and Nyquist bins are both real, and packed into the first bin.
+            self.scratch[0].re += spectrum[0].re * partition[0].re;
+            self.scratch[0].im += spectrum[0].im * partition[0].im;
+            for ((acc, x), h) in self.scratch[1..]
+                .iter_mut()
+                .zip(&spectrum[1..])
+                .zip(&partition[1..])
+            {
+                *acc += *x * *h;
+            }
+        }
+        self.fft.inverse(&mut self.scratch);
+
+        // Overlap-save: only the second half of the block is free of circular aliasing.
+        for (n, value) in self.scratch[B / 2..].iter().enumerate() {
+            self.output[2 * n] = value.re;
+            self.output[2 * n + 1] = value.im;
+        }
+    }
+
+    /// Convolve with a new impulse response, clearing the current state.
+    pub fn set_impulse_response(&mut self, impulse_response: &'static [f32]) {
+        self.impulse_response = impulse_response;
+        self.load();
+    }
+
+    /// Convolve the first partition of the impulse response directly, removing the latency of
+    /// processing by blocks. Clears the current state.
+    pub fn set_zero_latency(&mut self, zero_latency: bool) {
+        self.zero_latency = zero_latency;
+        self.load();
+    }
+
+    /// Transform the partitions of the impulse response.
+    fn load(&mut self) {
+        let ir = self.impulse_response;
+        let tail = if self.zero_latency {
+            self.head_length = ir.len().min(B);
+            self.head = [0.0; B];
+            self.head[..self.head_length].copy_from_slice(&ir[..self.head_length]);
+            &ir[self.head_length..]
+        } else {
+            self.head_length = 0;
+            ir
+        };
+
+        // The direct head takes the place of the first partition.
+        let capacity = if self.zero_latency { P - 1 } else { P };
+        self.num_partitions = tail.len().div_ceil(B).min(capacity);
+        for (partition, taps) in self.partitions.iter_mut().zip(tail.chunks(B)) {
+            // The taps fill the first half of the transform, and the second half is zero.
+            *partition = [Complex::ZERO; B];
+            for (value, pair) in partition.iter_mut().zip(taps.chunks(2)) {
+                *value = Complex::new(pair[0], pair.get(1).copied().unwrap_or(0.0));
+            }
+            self.fft.forward(partition);
+        }
+
+        self.reset();
+    }
+
+    /// Delay in samples of the output, which is `B` unless in the zero-latency mode.
+    pub fn latency(&self) -> usize {
+        if self.zero_latency { 0 } else { B }
+    }
+
+    /// Reset the convolver by clearing the input and output blocks, without changing the impulse
+    /// response.
+    pub fn reset(&mut self) -> &mut Self {
+        self.delay = [[Complex::ZERO; B]; P];
+        self.delay_index = 0;
+        self.input = [[0.0; B]; 2];
+        self.output = [0.0; B];
+        self.position = 0;
+        self.history = [0.0; B];
+        self
+    }
+}
+
+impl<const B: usize, const P: usize> Default for Convolver<B, P> {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const B: usize, const P: usize> AudioNode<f32, f32> for Convolver<B, P> {
+    fn tick(&mut self, input: &f32) -> f32 {
+        Convolver::tick(self, *input)
+    }
+
+    fn latency(&self) -> usize {
+        Convolver::latency(self)
+    }
+}
@@ -1,9 +1,14 @@
-use crate::PCM;
+use crate::{OutOfRange, PCM};
 
 use core::cmp::min;
 
 /// Fixed-length delay line with a capacity of `N` samples.
 ///
+/// Samples written with [`DelayLine::write_f32`] are converted into the encoding with the
+/// out-of-range policy of the line. By default there is no policy, and samples are converted with
+/// `From<f32>`: unquantized encodings such as `f32` pass through unchanged, which keeps the
+/// headroom of feedback paths, while integer encodings clamp.
+///
 /// # Examples
 ///
 /// ```
@@ -23,6 +28,7 @@ pub struct DelayLine<S: PCM, const N: usize> {
     buffer: [S; N],
     index: usize,
     size: usize,
+    policy: Option<OutOfRange>,
 }
 
 impl<S: PCM, const N: usize> DelayLine<S, N> {
@@ -33,6 +39,7 @@ impl<S: PCM, const N: usize> DelayLine<S, N> {
             buffer,
             index: 0,
             size,
+            policy: None,
         }
     }
 
@@ -73,8 +80,14 @@ impl<S: PCM, const N: usize> DelayLine<S, N> {
     /// the capacity of the audio buffer.
     #[inline(always)]
     pub fn set_length(&mut self, sec: f32, sample_rate: usize) {
-        let new_length = (sec * sample_rate as f32) as usize;
-        let new_length = min(new_length, self.buffer.len());
+        self.resize((sec * sample_rate as f32) as usize);
+    }
+
+    /// Set the delay line length in samples. Overflows will be clamped to the capacity of the audio
+    /// buffer.
+    #[inline(always)]
+    pub fn resize(&mut self, size: usize) {
+        let new_length = min(size, self.buffer.len());
 
         // Zero out any new values added to the buffer
         // if self.size < new_length {
@@ -99,12 +112,113 @@ impl<S: PCM, const N: usize> DelayLine<S, N> {
         self.buffer[self.index] = val;
     }
 
+    /// Convert a sample into the encoding with the out-of-range policy, and write it to the
+    /// current index.
+    ///
+    /// ```
+    /// use dspkit::components::DelayLine;
+    /// use dspkit::{I16, OutOfRange};
+    ///
+    /// // Without a policy, f32 samples keep their headroom and integer samples clamp.
+    /// let mut line = DelayLine::<f32, 4>::const_default();
+    /// line.write_f32(1.5);
+    /// assert_eq!(line.peek(), 1.5);
+    ///
+    /// let mut line = DelayLine::<I16, 4>::const_default();
+    /// line.write_f32(1.5);
+    /// assert_eq!(line.peek(), I16(i16::MAX));
+    ///
+    /// line.set_policy(Some(OutOfRange::Wrap));
+    /// line.write_f32(1.5);
+    /// assert_eq!(line.peek(), I16(-16_384));
+    /// ```
+    #[inline(always)]
+    pub fn write_f32(&mut self, val: f32) {
+        self.buffer[self.index] = self.encode(val);
+    }
+
+    /// Convert a sample into the encoding with the out-of-range policy of the line.
+    #[inline(always)]
+    pub fn encode(&self, val: f32) -> S {
+        match self.policy {
+            Some(policy) => S::from_f32_with(val, policy),
+            None => S::from(val),
+        }
+    }
+
+    /// The policy applied to samples outside of the range `-1.0..=1.0` by
+    /// [`DelayLine::write_f32`], or `None` to convert with `From<f32>`.
+    #[inline(always)]
+    pub fn policy(&self) -> Option<OutOfRange> {
+        self.policy
+    }
+
+    /// Set the policy applied to samples outside of the range `-1.0..=1.0` by
+    /// [`DelayLine::write_f32`], or `None` to convert with `From<f32>`.
+    pub fn set_policy(&mut self, policy: Option<OutOfRange>) {
+        self.policy = policy;
+    }
+
     /// Read the value at the current index.
     #[inline(always)]
     pub fn peek(&self) -> S {
         self.buffer[self.index]
     }
 
+    /// Read the value written `delay` samples ago, relative to the current index. Valid delays are
+    /// in the range `1..=size`, where a delay of `size` is equivalent to [`DelayLine::peek`].
+    ///
+    /// ```
+    /// use dspkit::components::DelayLine;
+    ///
+    /// let mut line = DelayLine::<f32, 4>::const_default();
+    /// for val in [1.0, 2.0, 3.0] {
+    ///     line.write(val);
+    ///     line.advance();
+    /// }
+    ///
+    /// assert_eq!(line.tap(1), 3.0);
+    /// assert_eq!(line.tap(3), 1.0);
+    /// ```
+    #[inline(always)]
+    pub fn tap(&self, delay: usize) -> S {
+        let delay = delay.max(1).min(self.size);
+        let index = if delay > self.index {
+            self.index + self.size - delay
+        } else {
+            self.index - delay
+        };
+        self.buffer[index]
+    }
+
+    /// Read a fractional delay using linear interpolation between neighbouring samples. The delay
+    /// is clamped to the range `1.0..=size`.
+    #[inline(always)]
+    pub fn tap_fractional(&self, delay: f32) -> f32 {
+        let delay = delay.max(1.0).min(self.size as f32);
+        let whole = delay as usize;
+        let frac = delay - whole as f32;
+
+        let a: f32 = self.tap(whole).into();
+        if frac == 0.0 {
+            return a;
+        }
+        let b: f32 = self.tap(whole + 1).into();
+        a + frac * (b - a)
+    }
+
+    /// The effective length of the delay line in samples.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns `true` if the delay line has an effective length of zero.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
     /// Advance the delay line, wrapping as a circular buffer if necessary.
     #[inline(always)]
     pub fn advance(&mut self) {
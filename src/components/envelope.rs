@@ -0,0 +1,83 @@
+use libm::expf;
+
+/// One-pole envelope follower with separate attack and release times.
+///
+/// The attack time is used while the input rises above the envelope, and the release time while it
+/// falls below. Feeding the absolute value of a signal gives a peak follower, while feeding its
+/// square gives a smoothed power estimate.
+///
+/// ```
+/// use dspkit::components::EnvelopeFollower;
+///
+/// let mut follower = EnvelopeFollower::const_default();
+/// follower.set_attack(0.0, 48_000);
+/// follower.set_release(100.0, 48_000);
+///
+/// assert_eq!(follower.tick(1.0), 1.0); // Instant attack.
+/// assert!(follower.tick(0.0) > 0.99); // Slow release.
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct EnvelopeFollower {
+    attack: f32,
+    release: f32,
+    value: f32,
+}
+
+impl EnvelopeFollower {
+    /// Default const constructor, i.e. can be created at compile-time. The attack and release are
+    /// instantaneous until set.
+    pub const fn const_default() -> Self {
+        Self {
+            attack: 0.0,
+            release: 0.0,
+            value: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let coefficient = if input > self.value {
+            self.attack
+        } else {
+            self.release
+        };
+        self.value = input + coefficient * (self.value - input);
+        self.value
+    }
+
+    /// The current value of the envelope.
+    #[inline(always)]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Set the attack time constant in milliseconds. 0.0 = instantaneous.
+    pub fn set_attack(&mut self, ms: f32, sample_rate: usize) {
+        self.attack = time_coefficient(ms, sample_rate);
+    }
+
+    /// Set the release time constant in milliseconds. 0.0 = instantaneous.
+    pub fn set_release(&mut self, ms: f32, sample_rate: usize) {
+        self.release = time_coefficient(ms, sample_rate);
+    }
+
+    pub fn reset(&mut self) {
+        self.value = 0.0;
+    }
+}
+
+impl Default for EnvelopeFollower {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+/// One-pole smoothing coefficient for a time constant in milliseconds.
+#[inline(always)]
+fn time_coefficient(ms: f32, sample_rate: usize) -> f32 {
+    if ms <= 0.0 {
+        0.0
+    } else {
+        expf(-1000.0 / (ms * sample_rate as f32))
+    }
+}
@@ -0,0 +1,144 @@
+use crate::components::WhiteNoise;
+
+use core::f32::consts::PI;
+use libm::{cosf, sinf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+const INITIAL_FREQUENCY: f32 = 1.0;
+
+/// Waveform produced by an [`Lfo`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LfoShape {
+    /// Smooth sinusoidal modulation.
+    Sine,
+    /// Linear ramps up and down.
+    Triangle,
+    /// Smoothly interpolated random values, choosing a new target once per cycle.
+    Random,
+}
+
+/// Low frequency oscillator with a bipolar output in the range `-1.0..=1.0`.
+///
+/// ```
+/// use dspkit::components::{Lfo, LfoShape};
+///
+/// let mut lfo = Lfo::new(LfoShape::Sine, 1.0, 48_000);
+/// lfo.set_phase(0.25);
+///
+/// assert!((lfo.tick() - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Lfo {
+    shape: LfoShape,
+    phase: f32,
+    frequency: f32,
+    sample_rate: f32,
+    phase_delta: f32,
+    noise: WhiteNoise,
+    random_from: f32,
+    random_to: f32,
+}
+
+impl Lfo {
+    pub const fn new(shape: LfoShape, frequency: f32, sample_rate: usize) -> Self {
+        let sample_rate = sample_rate as f32;
+        Self {
+            shape,
+            phase: 0.0,
+            frequency,
+            sample_rate,
+            phase_delta: frequency / sample_rate,
+            noise: WhiteNoise::new(0),
+            random_from: 0.0,
+            random_to: 0.0,
+        }
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        Self::new(LfoShape::Sine, INITIAL_FREQUENCY, INITIAL_SAMPLE_RATE)
+    }
+
+    /// Generate the current output value and advance the phase.
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        let out = self.value();
+
+        self.phase += self.phase_delta;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+            self.random_from = self.random_to;
+            self.random_to = self.noise.tick();
+        }
+
+        out
+    }
+
+    /// The current output value, without advancing the phase.
+    #[inline(always)]
+    pub fn value(&self) -> f32 {
+        match self.shape {
+            LfoShape::Sine => sinf(2.0 * PI * self.phase),
+            LfoShape::Triangle => {
+                if self.phase < 0.25 {
+                    4.0 * self.phase
+                } else if self.phase < 0.75 {
+                    2.0 - 4.0 * self.phase
+                } else {
+                    4.0 * self.phase - 4.0
+                }
+            }
+            LfoShape::Random => {
+                // Cosine interpolation keeps the slope continuous between random targets.
+                let t = 0.5 - 0.5 * cosf(PI * self.phase);
+                self.random_from + t * (self.random_to - self.random_from)
+            }
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate as f32;
+        self.phase_delta = self.frequency / self.sample_rate;
+    }
+
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+        self.random_from = 0.0;
+        self.random_to = 0.0;
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+        self.phase_delta = self.frequency / self.sample_rate;
+    }
+
+    pub fn set_shape(&mut self, shape: LfoShape) {
+        self.shape = shape;
+    }
+
+    /// The current phase, in cycles.
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    /// Set the phase, in cycles. Values outside `0.0..1.0` are wrapped.
+    pub const fn set_phase(&mut self, phase: f32) {
+        let wrapped = phase - (phase as i32) as f32;
+        self.phase = if wrapped < 0.0 {
+            wrapped + 1.0
+        } else {
+            wrapped
+        };
+    }
+
+    /// Seed the generator used by [`LfoShape::Random`].
+    pub const fn set_seed(&mut self, seed: u32) {
+        self.noise.reseed(seed);
+    }
+}
+
+impl Default for Lfo {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
@@ -0,0 +1,66 @@
+const INITIAL_SEED: u32 = 0x9E37_79B9;
+
+/// Deterministic white noise generator based on a 32-bit xorshift PRNG.
+///
+/// The same seed always produces the same sequence, which keeps noise-driven effects reproducible.
+///
+/// ```
+/// use dspkit::components::WhiteNoise;
+///
+/// let mut a = WhiteNoise::new(1234);
+/// let mut b = WhiteNoise::new(1234);
+///
+/// for _ in 0..64 {
+///     let val = a.tick();
+///     assert_eq!(val, b.tick());
+///     assert!((-1.0..1.0).contains(&val));
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct WhiteNoise {
+    state: u32,
+}
+
+impl WhiteNoise {
+    /// Construct a noise generator from a seed. A seed of zero is replaced with a fixed non-zero
+    /// seed, since xorshift would otherwise only produce zeros.
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { INITIAL_SEED } else { seed },
+        }
+    }
+
+    /// Generate the next raw 32-bit random value.
+    #[inline(always)]
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Generate a uniformly distributed value in the range `0.0..1.0`.
+    #[inline(always)]
+    pub fn next_unipolar(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Generate a uniformly distributed value in the range `-1.0..1.0`.
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        2.0 * self.next_unipolar() - 1.0
+    }
+
+    /// Restart the sequence from a new seed.
+    pub const fn reseed(&mut self, seed: u32) {
+        *self = Self::new(seed);
+    }
+}
+
+impl Default for WhiteNoise {
+    fn default() -> Self {
+        Self::new(INITIAL_SEED)
+    }
+}
@@ -0,0 +1,280 @@
+use crate::AudioNode;
+
+use libm::roundf;
+
+/// Number of distinct coefficients of the halfband filter. The filter is symmetric, so this is
+/// half the taps of its polyphase branch.
+const HALF_TAPS: usize = 12;
+
+/// Number of taps of the polyphase branch of the halfband filter.
+const BRANCH_TAPS: usize = 2 * HALF_TAPS;
+
+/// Coefficients of the polyphase branch of the halfband filter, up to its center. The other branch
+/// is a single tap of 0.5 at the center of the filter, as every other coefficient of a halfband
+/// filter is zero. The filter is a Kaiser-windowed sinc (beta = 7.0) with 47 taps, attenuating by
+/// at least 70 dB above 0.6 times the lower Nyquist frequency, with a passband flat to 0.4 times
+/// the lower sample rate.
+const HALFBAND: [f32; HALF_TAPS] = [
+    -8.20876e-5,
+    0.00039050973,
+    -0.0010708485,
+    0.0023473979,
+    -0.00451321,
+    0.007952739,
+    -0.013204763,
+    0.021137198,
+    -0.03346171,
+    0.054532588,
+    -0.10039157,
+    0.31636375,
+];
+
+/// Delay in samples at the higher rate of each halfband filter.
+const HALFBAND_DELAY: usize = BRANCH_TAPS - 1;
+
+/// Maximum number of cascaded 2x stages.
+const MAX_STAGES: usize = 4;
+
+/// Maximum oversampling factor.
+const MAX_FACTOR: usize = 1 << MAX_STAGES;
+
+/// Audio node running an inner node at a multiple of the sample rate, to suppress the aliasing of
+/// nonlinear processing.
+///
+/// The oversampling `FACTOR` must be a power of two up to [`Oversampled::MAX_FACTOR`]. Each factor
+/// of two is a stage of polyphase halfband FIR filters, which interpolate the `C` channels of the
+/// input up to the higher rate, and then filter and decimate the output of the inner node back
+/// down. The inner node is prepared with the multiplied sample rate. The filters have linear phase,
+/// which adds latency but keeps the oversampled signal phase-aligned across channels.
+///
+/// ```
+/// use dspkit::AudioNode;
+/// use dspkit::components::Oversampled;
+/// use dspkit::effects::Distortion;
+///
+/// static DISTORTION: Oversampled<Distortion, 4> = Oversampled::new(Distortion::const_default());
+///
+/// // Oversampling a node which passes the signal through only delays the signal.
+/// struct Passthrough;
+///
+/// impl AudioNode<[f32; 1], [f32; 1]> for Passthrough {
+///     fn tick(&mut self, input: &[f32; 1]) -> [f32; 1] {
+///         *input
+///     }
+/// }
+///
+/// let mut oversampled = Oversampled::<_, 2, 1>::new(Passthrough);
+/// let latency = oversampled.latency();
+///
+/// let input = |n: usize| libm::sinf(0.1 * n as f32);
+/// for n in 0..1_000 {
+///     let out = oversampled.tick(&[input(n)]);
+///     // Skip the filters filling up with the signal.
+///     if n >= 2 * latency {
+///         assert!((out[0] - input(n - latency)).abs() < 1e-3);
+///     }
+/// }
+/// ```
+pub struct Oversampled<N, const FACTOR: usize, const C: usize = 2> {
+    inner: N,
+    channels: [OversampledChannel; C],
+}
+
+/// Filter state of a single channel for each stage.
+#[derive(Clone, Copy)]
+struct OversampledChannel {
+    up: [Upsampler; MAX_STAGES],
+    down: [Downsampler; MAX_STAGES],
+}
+
+impl<N, const FACTOR: usize, const C: usize> Oversampled<N, FACTOR, C> {
+    /// Maximum supported oversampling factor.
+    pub const MAX_FACTOR: usize = MAX_FACTOR;
+
+    /// Number of cascaded 2x stages.
+    const STAGES: usize = FACTOR.trailing_zeros() as usize;
+
+    /// Wrap the inner node. The inner node must be prepared through [`Oversampled::prepare`] so
+    /// it runs at the multiplied sample rate.
+    pub const fn new(inner: N) -> Self {
+        const {
+            assert!(
+                FACTOR.is_power_of_two() && FACTOR <= Self::MAX_FACTOR,
+                "oversampling factor must be a power of two up to MAX_FACTOR"
+            );
+        }
+
+        Self {
+            inner,
+            channels: [OversampledChannel::const_default(); C],
+        }
+    }
+
+    /// Access the inner node, e.g. to set its parameters.
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    /// Mutably access the inner node, e.g. to set its parameters.
+    pub fn inner_mut(&mut self) -> &mut N {
+        &mut self.inner
+    }
+
+    /// Reset the oversampling filters. The inner node is left unchanged.
+    pub fn reset(&mut self) -> &mut Self {
+        self.channels = [OversampledChannel::const_default(); C];
+        self
+    }
+}
+
+impl<N, const FACTOR: usize, const C: usize> AudioNode<[f32; C], [f32; C]>
+    for Oversampled<N, FACTOR, C>
+where
+    N: AudioNode<[f32; C], [f32; C]>,
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        self.inner.prepare(sample_rate * FACTOR);
+    }
+
+    fn tick(&mut self, input: &[f32; C]) -> [f32; C] {
+        let mut frames = [[0.0; C]; MAX_FACTOR];
+        frames[0] = *input;
+
+        for stage in 0..Self::STAGES {
+            let previous = frames;
+            for idx in 0..1 << stage {
+                for (ch, channel) in self.channels.iter_mut().enumerate() {
+                    let [even, odd] = channel.up[stage].tick(previous[idx][ch]);
+                    frames[2 * idx][ch] = even;
+                    frames[2 * idx + 1][ch] = odd;
+                }
+            }
+        }
+
+        for frame in frames[..FACTOR].iter_mut() {
+            *frame = self.inner.tick(frame);
+        }
+
+        // Decimate in place, walking forwards so each pair is read before it is overwritten.
+        for stage in (0..Self::STAGES).rev() {
+            for idx in 0..1 << stage {
+                for (ch, channel) in self.channels.iter_mut().enumerate() {
+                    frames[idx][ch] =
+                        channel.down[stage].tick(frames[2 * idx][ch], frames[2 * idx + 1][ch]);
+                }
+            }
+        }
+
+        frames[0]
+    }
+
+    /// Delay in samples between the input and output signals, including the latency of the inner
+    /// node, rounded to the nearest sample.
+    fn latency(&self) -> usize {
+        // Each stage delays by the interpolation and decimation filters at its higher rate.
+        let filters: f32 = (1..=Self::STAGES)
+            .map(|stage| (2 * HALFBAND_DELAY) as f32 / (1 << stage) as f32)
+            .sum();
+        let inner = self.inner.latency() as f32 / FACTOR as f32;
+        roundf(filters + inner) as usize
+    }
+}
+
+impl OversampledChannel {
+    const fn const_default() -> Self {
+        Self {
+            up: [Upsampler::const_default(); MAX_STAGES],
+            down: [Downsampler::const_default(); MAX_STAGES],
+        }
+    }
+}
+
+/// Interpolates a signal by a factor of 2 with the halfband filter.
+#[derive(Clone, Copy)]
+struct Upsampler {
+    history: History,
+}
+
+impl Upsampler {
+    const fn const_default() -> Self {
+        Self {
+            history: History::const_default(),
+        }
+    }
+
+    /// Interpolate a sample into two samples at the higher rate.
+    #[inline(always)]
+    fn tick(&mut self, input: f32) -> [f32; 2] {
+        let history = self.history.push(input);
+        // The zero-stuffed input is scaled by 2 to keep unity gain, which cancels the center tap
+        // of 0.5.
+        [2.0 * convolve(history), history[HALF_TAPS - 1]]
+    }
+}
+
+/// Decimates a signal by a factor of 2 with the halfband filter.
+#[derive(Clone, Copy)]
+struct Downsampler {
+    even: History,
+    odd: History,
+}
+
+impl Downsampler {
+    const fn const_default() -> Self {
+        Self {
+            even: History::const_default(),
+            odd: History::const_default(),
+        }
+    }
+
+    /// Decimate two consecutive samples at the higher rate into a single sample.
+    #[inline(always)]
+    fn tick(&mut self, even: f32, odd: f32) -> f32 {
+        // The center tap falls on the odd sample from half the filter length ago.
+        let center = self.odd.newest(HALF_TAPS - 1);
+        self.odd.push(odd);
+        convolve(self.even.push(even)) + 0.5 * center
+    }
+}
+
+/// History of the most recent samples, stored twice in a row so the samples can be read as a
+/// contiguous slice without wrapping.
+#[derive(Clone, Copy)]
+struct History {
+    buffer: [f32; 2 * BRANCH_TAPS],
+    pos: usize,
+}
+
+impl History {
+    const fn const_default() -> Self {
+        Self {
+            buffer: [0.0; 2 * BRANCH_TAPS],
+            pos: 0,
+        }
+    }
+
+    /// Push a sample and return the history, newest sample first.
+    #[inline(always)]
+    fn push(&mut self, input: f32) -> &[f32] {
+        self.pos = self.pos.checked_sub(1).unwrap_or(BRANCH_TAPS - 1);
+        self.buffer[self.pos] = input;
+        self.buffer[self.pos + BRANCH_TAPS] = input;
+        &self.buffer[self.pos..self.pos + BRANCH_TAPS]
+    }
+
+    /// The sample pushed `age` samples before the newest sample.
+    #[inline(always)]
+    fn newest(&self, age: usize) -> f32 {
+        self.buffer[self.pos + age]
+    }
+}
+
+/// Apply the polyphase branch of the halfband filter to a history, newest sample first.
+#[inline(always)]
+fn convolve(history: &[f32]) -> f32 {
+    HALFBAND
+        .iter()
+        .enumerate()
+        .map(|(idx, coeff)| coeff * (history[idx] + history[BRANCH_TAPS - 1 - idx]))
+        .sum()
+}
@@ -0,0 +1,253 @@
+use crate::components::WhiteNoise;
+use crate::{AudioNode, OutOfRange, PCM};
+
+use core::marker::PhantomData;
+use libm::roundf;
+
+/// Seed of the dither noise of a default quantizer.
+const INITIAL_SEED: u32 = 0xD17E_5EED;
+
+/// Maximum number of taps of the noise shaping filters.
+const MAX_TAPS: usize = 9;
+
+/// Largest error in quantization steps fed back into the noise shaping filter. Clipping produces
+/// errors far larger than a step, which would otherwise make the feedback unstable.
+const MAX_ERROR: f32 = 4.0;
+
+/// Error feedback coefficients of the 5-tap noise shaping filter by Lipshitz et al.
+const LIPSHITZ: [f32; 5] = [2.033, -2.165, 1.959, -1.590, 0.6149];
+
+/// Error feedback coefficients of the 9-tap F-weighted noise shaping filter by Wannamaker.
+const F_WEIGHTED: [f32; 9] = [
+    2.412, -3.370, 3.937, -4.174, 3.353, -2.205, 1.281, -0.569, 0.0847,
+];
+
+/// Converts frames of `C` channels into a lower precision [`PCM`] encoding, with dither and noise
+/// shaping.
+///
+/// Dither decorrelates the quantization error from the signal, trading harmonic distortion for a
+/// constant noise floor. Noise shaping feeds the error back through a filter, moving the noise
+/// away from the frequencies where hearing is most sensitive. The dither is generated from a
+/// seed, so the same input and seed always produce the same output.
+///
+/// ```
+/// use dspkit::components::{Dither, NoiseShaping, Quantizer, QuantizerParameters};
+/// use dspkit::{I16, PCM};
+///
+/// static QUANTIZER: Quantizer<I16, 2> = Quantizer::const_default();
+///
+/// let parameters = QuantizerParameters {
+///     dither: Dither::Triangular,
+///     noise_shaping: NoiseShaping::Lipshitz,
+///     ..QuantizerParameters::default()
+/// };
+/// let mut a = Quantizer::<I16, 2>::new(parameters);
+/// let mut b = Quantizer::<I16, 2>::new(parameters);
+///
+/// // The same seed produces the same output, and silence stays within a few steps of zero.
+/// for n in 0..1_000 {
+///     let x = if n < 500 { 0.5 * libm::sinf(0.01 * n as f32) } else { 0.0 };
+///     let out = a.tick(&[x, x]);
+///     assert_eq!(out, b.tick(&[x, x]));
+///     if n > 600 {
+///         assert!(out[0].0.abs() <= 16);
+///     }
+/// }
+/// ```
+pub struct Quantizer<S: PCM, const C: usize> {
+    parameters: QuantizerParameters,
+    noise: WhiteNoise,
+    channels: [QuantizerChannel; C],
+    encoding: PhantomData<S>,
+}
+
+/// Probability distribution of the dither added before quantization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dither {
+    /// Quantize without dither.
+    None,
+    /// Rectangular dither of +/- half a step. Removes the distortion, but leaves the noise power
+    /// dependent on the signal.
+    Rectangular,
+    /// Triangular dither of +/- one step. Removes the distortion and makes the noise power
+    /// independent of the signal.
+    Triangular,
+    /// Triangular dither with a high-pass spectrum, from the difference of consecutive
+    /// rectangular values. Less audible than [`Dither::Triangular`] at the same power.
+    HighPassTriangular,
+}
+
+/// Filter applied to the quantization error.
+///
+/// The filters are designed for a sample rate of 44.1 kHz, and shift the noise progressively
+/// higher at higher sample rates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoiseShaping {
+    /// Leave the quantization noise white.
+    None,
+    /// First-order high-pass shaping of the noise.
+    FirstOrder,
+    /// 5-tap filter by Lipshitz, Vanderkooy and Wannamaker.
+    Lipshitz,
+    /// 9-tap filter by Wannamaker following the F-weighted curve of equal loudness.
+    FWeighted,
+}
+
+/// Parameters to the quantizer.
+#[derive(Clone, Copy)]
+pub struct QuantizerParameters {
+    /// Dither added before quantization.
+    pub dither: Dither,
+    /// Filter applied to the quantization error.
+    pub noise_shaping: NoiseShaping,
+    /// Policy for samples outside of the range of the encoding.
+    pub out_of_range: OutOfRange,
+    /// Seed of the dither noise.
+    pub seed: u32,
+}
+
+/// Error history and dither state of a single channel.
+#[derive(Clone, Copy)]
+struct QuantizerChannel {
+    /// Previous errors, newest first.
+    errors: [f32; MAX_TAPS],
+    /// Previous rectangular dither value, for high-pass triangular dither.
+    last_dither: f32,
+}
+
+impl<S: PCM, const C: usize> Quantizer<S, C> {
+    /// Construct a quantizer with the given initial parameters.
+    pub fn new(parameters: QuantizerParameters) -> Self {
+        let mut quantizer = Self::const_default();
+        quantizer.parameters = parameters;
+        quantizer.set_seed(parameters.seed);
+        quantizer
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: QuantizerParameters::const_default(),
+            noise: WhiteNoise::new(INITIAL_SEED),
+            channels: [QuantizerChannel::const_default(); C],
+            encoding: PhantomData,
+        }
+    }
+
+    pub fn tick(&mut self, input: &[f32; C]) -> [S; C] {
+        let step = S::PCM_STEP;
+        let coefficients = self.parameters.noise_shaping.coefficients();
+        let mut output = [S::PCM_EQUILIBRIUM; C];
+
+        for (ch, channel) in self.channels.iter_mut().enumerate() {
+            let shaped = coefficients
+                .iter()
+                .zip(channel.errors.iter())
+                .fold(input[ch], |acc, (coeff, error)| acc - coeff * error);
+
+            let dither = match self.parameters.dither {
+                Dither::None => 0.0,
+                Dither::Rectangular => self.noise.next_unipolar() - 0.5,
+                Dither::Triangular => self.noise.next_unipolar() - self.noise.next_unipolar(),
+                Dither::HighPassTriangular => {
+                    let current = self.noise.next_unipolar() - 0.5;
+                    let dither = current - channel.last_dither;
+                    channel.last_dither = current;
+                    dither
+                }
+            };
+
+            let quantized = if step > 0.0 {
+                roundf(shaped / step + dither) * step
+            } else {
+                shaped
+            };
+            let sample = S::from_f32_with(quantized, self.parameters.out_of_range);
+            output[ch] = sample;
+
+            let limit = MAX_ERROR * step;
+            let error = (sample.into() - shaped).clamp(-limit, limit);
+            channel.errors.copy_within(..MAX_TAPS - 1, 1);
+            channel.errors[0] = error;
+        }
+
+        output
+    }
+
+    pub fn set_dither(&mut self, val: Dither) {
+        self.parameters.dither = val;
+    }
+
+    pub fn set_noise_shaping(&mut self, val: NoiseShaping) {
+        self.parameters.noise_shaping = val;
+    }
+
+    pub fn set_out_of_range(&mut self, val: OutOfRange) {
+        self.parameters.out_of_range = val;
+    }
+
+    /// Restart the dither noise from a new seed.
+    pub fn set_seed(&mut self, val: u32) {
+        self.parameters.seed = val;
+        self.noise.reseed(val);
+    }
+
+    /// Reset the quantizer by clearing the error history and restarting the dither noise from the
+    /// seed.
+    pub fn reset(&mut self) -> &mut Self {
+        self.channels = [QuantizerChannel::const_default(); C];
+        self.noise.reseed(self.parameters.seed);
+        self
+    }
+}
+
+impl NoiseShaping {
+    /// Error feedback coefficients, applied to the previous errors, newest first.
+    #[inline(always)]
+    fn coefficients(&self) -> &'static [f32] {
+        match self {
+            Self::None => &[],
+            Self::FirstOrder => &[1.0],
+            Self::Lipshitz => &LIPSHITZ,
+            Self::FWeighted => &F_WEIGHTED,
+        }
+    }
+}
+
+impl QuantizerChannel {
+    const fn const_default() -> Self {
+        Self {
+            errors: [0.0; MAX_TAPS],
+            last_dither: 0.0,
+        }
+    }
+}
+
+impl QuantizerParameters {
+    pub const fn const_default() -> Self {
+        QuantizerParameters {
+            dither: Dither::Triangular,
+            noise_shaping: NoiseShaping::None,
+            out_of_range: OutOfRange::Clamp,
+            seed: INITIAL_SEED,
+        }
+    }
+}
+
+impl Default for QuantizerParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const C: usize> Default for Quantizer<S, C> {
+    fn default() -> Self {
+        Self::new(QuantizerParameters::const_default())
+    }
+}
+
+impl<S: PCM, const C: usize> AudioNode<[f32; C], [S; C]> for Quantizer<S, C> {
+    fn tick(&mut self, input: &[f32; C]) -> [S; C] {
+        Quantizer::tick(self, input)
+    }
+}
@@ -0,0 +1,89 @@
+use crate::components::DelayLine;
+
+use libm::sqrtf;
+
+/// Windowed RMS level detector with a window of up to `N` samples.
+///
+/// The squared input is kept in a delay line spanning the window, and a running sum adds the
+/// newest square while subtracting the one leaving the window. The sum is recomputed once per
+/// window so rounding errors cannot accumulate.
+///
+/// ```
+/// use dspkit::components::RmsDetector;
+///
+/// let mut rms = RmsDetector::<480>::const_default();
+/// rms.set_window(10.0, 48_000);
+///
+/// let mut level = 0.0;
+/// for n in 0..4_800 {
+///     level = rms.tick(&if n % 2 == 0 { 0.5 } else { -0.5 });
+/// }
+///
+/// assert!((level - 0.5).abs() < 1e-4);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RmsDetector<const N: usize> {
+    line: DelayLine<f32, N>,
+    sum: f32,
+    counter: usize,
+}
+
+impl<const N: usize> RmsDetector<N> {
+    /// Default const constructor, i.e. can be created at compile-time. The window spans the full
+    /// capacity of `N` samples.
+    pub const fn const_default() -> Self {
+        Self {
+            line: DelayLine::const_default(),
+            sum: 0.0,
+            counter: 0,
+        }
+    }
+
+    /// Push a sample and return the RMS level over the window.
+    #[inline(always)]
+    pub fn tick(&mut self, input: &f32) -> f32 {
+        let square = input * input;
+        self.sum += square - self.line.peek();
+        self.line.write(square);
+        self.line.advance();
+
+        self.counter += 1;
+        if self.counter >= self.line.len() {
+            self.counter = 0;
+            self.sum = (1..=self.line.len())
+                .map(|delay| self.line.tap(delay))
+                .sum();
+        }
+
+        self.value()
+    }
+
+    /// The RMS level over the window.
+    #[inline(always)]
+    pub fn value(&self) -> f32 {
+        if self.line.is_empty() {
+            0.0
+        } else {
+            sqrtf(self.sum.max(0.0) / self.line.len() as f32)
+        }
+    }
+
+    /// Set the window length in milliseconds, clamped to the capacity of `N` samples. Changing
+    /// the window clears the detector.
+    pub fn set_window(&mut self, ms: f32, sample_rate: usize) {
+        self.line.set_length(ms * 0.001, sample_rate);
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.line.reset();
+        self.sum = 0.0;
+        self.counter = 0;
+    }
+}
+
+impl<const N: usize> Default for RmsDetector<N> {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
@@ -0,0 +1,221 @@
+use crate::AudioNode;
+use crate::fft::{Complex, RealFft, Window};
+
+use libm::sqrtf;
+
+/// Processing applied to each spectrum of a [`Stft`].
+///
+/// The spectrum has the packed layout of [`RealFft`]: the bins `0..N`, with the real DC bin in the
+/// real part of the first value, and the real Nyquist bin in its imaginary part.
+///
+/// Any closure taking a mutable spectrum is a spectral processor.
+pub trait SpectralProcessor<const N: usize> {
+    /// Prepare the processor before processing, given the sample rate and the hop in samples
+    /// between consecutive spectra.
+    #[allow(unused_variables)]
+    fn prepare(&mut self, sample_rate: usize, hop: usize) {}
+
+    /// Process a spectrum in place.
+    fn process(&mut self, spectrum: &mut [Complex; N]);
+}
+
+impl<F: FnMut(&mut [Complex; N]), const N: usize> SpectralProcessor<N> for F {
+    fn process(&mut self, spectrum: &mut [Complex; N]) {
+        self(spectrum)
+    }
+}
+
+/// Short-time Fourier transform, running a [`SpectralProcessor`] on overlapping frames of `2 * N`
+/// samples, with a new frame every `HOP` samples.
+///
+/// Each frame of the input is windowed and transformed into a spectrum of `N` bins, which is
+/// processed and transformed back. The frames are windowed again and overlap-added into the
+/// output, normalized by the overlap of the windows so that a processor which leaves the spectrum
+/// untouched reproduces the input. The hop must divide the frame length, and the overlap of the
+/// windows should be enough for the window to add up smoothly, e.g. a hop of a quarter of the
+/// frame for [`Window::Hann`].
+///
+/// The output is delayed by the length of a frame, less one sample.
+///
+/// ```
+/// use dspkit::components::Stft;
+/// use dspkit::fft::{Complex, Window};
+///
+/// fn passthrough(_: &mut [Complex; 64]) {}
+///
+/// static STFT: Stft<fn(&mut [Complex; 64]), 64, 32> = Stft::new(passthrough, Window::Hann);
+///
+/// let mut stft = Stft::<_, 64, 32>::new(|_: &mut [Complex; 64]| {}, Window::Hann);
+/// stft.prepare(48_000);
+///
+/// let latency = stft.latency();
+/// let input = |n: usize| libm::sinf(0.05 * n as f32);
+/// for n in 0..1_000 {
+///     let output = stft.tick(input(n));
+///     if n >= latency + 128 {
+///         assert!((output - input(n - latency)).abs() < 1e-4);
+///     }
+/// }
+/// ```
+pub struct Stft<P: SpectralProcessor<N>, const N: usize, const HOP: usize> {
+    processor: P,
+    window: Window,
+    fft: RealFft<N>,
+    analysis: [[f32; N]; 2],
+    synthesis: [[f32; N]; 2],
+    input: [[f32; N]; 2],
+    output: [[f32; N]; 2],
+    spectrum: [Complex; N],
+    position: usize,
+    hop_position: usize,
+}
+
+impl<P: SpectralProcessor<N>, const N: usize, const HOP: usize> Stft<P, N, HOP> {
+    /// Construct an STFT around a spectral processor, i.e. can be created at compile-time. The
+    /// windows are computed in [`Stft::prepare`], which must be called before processing.
+    pub const fn new(processor: P, window: Window) -> Self {
+        const {
+            assert!(
+                HOP >= 1 && HOP <= 2 * N && (2 * N).is_multiple_of(HOP),
+                "hop must divide the frame length"
+            );
+        }
+
+        Self {
+            processor,
+            window,
+            fft: RealFft::new(),
+            analysis: [[0.0; N]; 2],
+            synthesis: [[0.0; N]; 2],
+            input: [[0.0; N]; 2],
+            output: [[0.0; N]; 2],
+            spectrum: [Complex::ZERO; N],
+            position: 0,
+            hop_position: 0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.set_window(self.window);
+        self.processor.prepare(sample_rate, HOP);
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let length = 2 * N;
+        self.input.as_flattened_mut()[self.position] = input;
+        self.position = (self.position + 1) % length;
+
+        self.hop_position += 1;
+        if self.hop_position == HOP {
+            self.hop_position = 0;
+            self.process_frame();
+        }
+
+        // The oldest sample of the output is complete, since no later frame overlaps it.
+        let output = self.output.as_flattened_mut();
+        let sample = output[self.position];
+        output[self.position] = 0.0;
+        sample
+    }
+
+    /// Transform, process and overlap-add the latest frame of input, which starts at the position.
+    fn process_frame(&mut self) {
+        let length = 2 * N;
+        let input = self.input.as_flattened();
+        let analysis = self.analysis.as_flattened();
+        for (n, value) in self.spectrum.iter_mut().enumerate() {
+            let even = (self.position + 2 * n) % length;
+            let odd = (even + 1) % length;
+            *value = Complex::new(
+                input[even] * analysis[2 * n],
+                input[odd] * analysis[2 * n + 1],
+            );
+        }
+
+        self.fft.forward(&mut self.spectrum);
+        self.processor.process(&mut self.spectrum);
+        self.fft.inverse(&mut self.spectrum);
+
+        let output = self.output.as_flattened_mut();
+        let synthesis = self.synthesis.as_flattened();
+        for (n, value) in self.spectrum.iter().enumerate() {
+            let even = (self.position + 2 * n) % length;
+            let odd = (even + 1) % length;
+            output[even] += value.re * synthesis[2 * n];
+            output[odd] += value.im * synthesis[2 * n + 1];
+        }
+    }
+
+    /// Set the window applied before the transform and again before the overlap-add.
+    pub fn set_window(&mut self, window: Window) {
+        self.window = window;
+        window.periodic(self.analysis.as_flattened_mut());
+
+        // Normalize the synthesis window by the overlap of the product of both windows at each
+        // position, so the frames add up to the input.
+        let analysis = self.analysis.as_flattened();
+        let synthesis = self.synthesis.as_flattened_mut();
+        for (n, value) in synthesis.iter_mut().enumerate() {
+            let overlap: f32 = analysis
+                .iter()
+                .skip(n % HOP)
+                .step_by(HOP)
+                .map(|w| w * w)
+                .sum();
+            *value = if overlap > 0.0 {
+                analysis[n] / overlap
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Gain which restores the level of spectra resynthesized with random phases, e.g. by a
+    /// freeze. The energy of such a frame is spread evenly over the frame rather than following
+    /// the analysis window, and consecutive frames add up without correlation.
+    pub fn random_phase_gain(&self) -> f32 {
+        let length = (2 * N) as f32;
+        let analysis: f32 = self.analysis.as_flattened().iter().map(|w| w * w).sum();
+        let synthesis: f32 = self.synthesis.as_flattened().iter().map(|w| w * w).sum();
+        let power = analysis / length * synthesis / HOP as f32;
+        if power > 0.0 { 1.0 / sqrtf(power) } else { 0.0 }
+    }
+
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+
+    /// Delay in samples of the output, which is the length of a frame less one sample.
+    pub fn latency(&self) -> usize {
+        2 * N - 1
+    }
+
+    /// Reset the STFT by clearing the input and output frames. The processor is not reset.
+    pub fn reset(&mut self) -> &mut Self {
+        self.input = [[0.0; N]; 2];
+        self.output = [[0.0; N]; 2];
+        self.position = 0;
+        self.hop_position = 0;
+        self
+    }
+}
+
+impl<P: SpectralProcessor<N>, const N: usize, const HOP: usize> AudioNode<f32, f32>
+    for Stft<P, N, HOP>
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        Stft::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &f32) -> f32 {
+        Stft::tick(self, *input)
+    }
+
+    fn latency(&self) -> usize {
+        Stft::latency(self)
+    }
+}
@@ -0,0 +1,135 @@
+/// Number of input samples used by each interpolation phase.
+const TAPS: usize = 12;
+
+/// Number of interpolated phases per input sample, i.e. the oversampling factor.
+const PHASES: usize = 4;
+
+/// Polyphase interpolation filters for the fractional phases `1/4`, `2/4` and `3/4`. Each phase is
+/// a Kaiser-windowed sinc (beta = 5.0) normalized to unity DC gain. The integer phase is the input
+/// itself, delayed by [`TruePeakDetector::LATENCY`] samples.
+const INTERPOLATION: [[f32; TAPS]; PHASES - 1] = [
+    [
+        -0.0022711647,
+        0.0088858055,
+        -0.022932814,
+        0.05004928,
+        -0.10608428,
+        0.29008368,
+        0.8977755,
+        -0.1634471,
+        0.072401956,
+        -0.03430083,
+        0.014719555,
+        -0.004879555,
+    ],
+    [
+        -0.0048438697,
+        0.016329488,
+        -0.039836273,
+        0.08515047,
+        -0.18444532,
+        0.6276455,
+        0.6276455,
+        -0.18444532,
+        0.08515047,
+        -0.039836273,
+        0.016329488,
+        -0.0048438697,
+    ],
+    [
+        -0.004879555,
+        0.014719555,
+        -0.03430083,
+        0.072401956,
+        -0.1634471,
+        0.8977755,
+        0.29008368,
+        -0.10608428,
+        0.05004928,
+        -0.022932814,
+        0.0088858055,
+        -0.0022711647,
+    ],
+];
+
+/// True-peak level detector in the style of ITU-R BS.1770.
+///
+/// The signal is oversampled by a factor of 4 with polyphase interpolation filters, and the peak
+/// is the largest absolute value among the interpolated samples. This catches inter-sample peaks
+/// which exceed the sample values once the signal is reconstructed by a DAC.
+///
+/// ```
+/// use dspkit::components::TruePeakDetector;
+///
+/// let mut detector = TruePeakDetector::const_default();
+///
+/// // A sine at a quarter of the sample rate, sampled 45 degrees off its peaks.
+/// let mut peak: f32 = 0.0;
+/// for n in 0..64 {
+///     let x = libm::sinf(core::f32::consts::FRAC_PI_2 * n as f32 + core::f32::consts::FRAC_PI_4);
+///     peak = peak.max(detector.tick(&x));
+/// }
+///
+/// assert!(peak > 0.95);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct TruePeakDetector {
+    history: [f32; TAPS],
+}
+
+impl TruePeakDetector {
+    /// Delay in samples between an input sample and the detected peak around it.
+    pub const LATENCY: usize = TAPS / 2;
+
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        Self {
+            history: [0.0; TAPS],
+        }
+    }
+
+    /// Push a sample and return the true peak around the sample from [`Self::LATENCY`] samples ago.
+    #[inline(always)]
+    pub fn tick(&mut self, input: &f32) -> f32 {
+        self.push(*input);
+        self.peak()
+    }
+
+    /// Push a sample into the detector without computing the peak.
+    #[inline(always)]
+    pub fn push(&mut self, input: f32) {
+        self.history.copy_within(0..TAPS - 1, 1);
+        self.history[0] = input;
+    }
+
+    /// The true peak between the sample from [`Self::LATENCY`] samples ago and its successor.
+    #[inline(always)]
+    pub fn peak(&self) -> f32 {
+        let mut peak = self.delayed().abs();
+        for phase in INTERPOLATION.iter() {
+            let val: f32 = phase
+                .iter()
+                .zip(self.history.iter())
+                .map(|(coefficient, x)| coefficient * x)
+                .sum();
+            peak = peak.max(val.abs());
+        }
+        peak
+    }
+
+    /// The input sample from [`Self::LATENCY`] samples ago.
+    #[inline(always)]
+    pub fn delayed(&self) -> f32 {
+        self.history[Self::LATENCY]
+    }
+
+    pub fn reset(&mut self) {
+        self.history = [0.0; TAPS];
+    }
+}
+
+impl Default for TruePeakDetector {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
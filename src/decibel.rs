@@ -0,0 +1,49 @@
+use libm::{log10f, powf};
+
+/// Lowest level in dB reported by the conversions, used in place of negative infinity for silence.
+pub const MIN_DB: f32 = -120.0;
+
+/// Convert a linear gain (amplitude) to decibels, limited to [`MIN_DB`].
+///
+/// ```
+/// use dspkit::{MIN_DB, gain_to_db};
+///
+/// assert!((gain_to_db(0.5) + 6.0206).abs() < 1e-3);
+/// assert_eq!(gain_to_db(0.0), MIN_DB);
+/// ```
+#[inline(always)]
+pub fn gain_to_db(gain: f32) -> f32 {
+    if gain <= 0.0 {
+        MIN_DB
+    } else {
+        (20.0 * log10f(gain)).max(MIN_DB)
+    }
+}
+
+/// Convert decibels to a linear gain (amplitude).
+///
+/// ```
+/// use dspkit::db_to_gain;
+///
+/// assert!((db_to_gain(-6.0206) - 0.5).abs() < 1e-4);
+/// ```
+#[inline(always)]
+pub fn db_to_gain(db: f32) -> f32 {
+    powf(10.0, db / 20.0)
+}
+
+/// Convert a power (e.g. a mean square) to decibels, limited to [`MIN_DB`].
+#[inline(always)]
+pub fn power_to_db(power: f32) -> f32 {
+    if power <= 0.0 {
+        MIN_DB
+    } else {
+        (10.0 * log10f(power)).max(MIN_DB)
+    }
+}
+
+/// Convert decibels to a power.
+#[inline(always)]
+pub fn db_to_power(db: f32) -> f32 {
+    powf(10.0, db / 10.0)
+}
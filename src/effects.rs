@@ -1,5 +1,27 @@
+mod bitcrusher;
+mod convolution;
 mod delay;
+mod distortion;
+mod dynamics;
+mod fdn;
 mod freeverb;
+mod phaser;
+mod pitch_shifter;
+mod plate;
+mod spectral;
+mod vibrato;
+mod vocoder;
 
+pub use bitcrusher::*;
+pub use convolution::*;
 pub use delay::*;
+pub use distortion::*;
+pub use dynamics::*;
+pub use fdn::*;
 pub use freeverb::*;
+pub use phaser::*;
+pub use pitch_shifter::*;
+pub use plate::*;
+pub use spectral::*;
+pub use vibrato::*;
+pub use vocoder::*;
@@ -0,0 +1,204 @@
+use crate::components::WhiteNoise;
+use crate::{AudioNode, Stereo};
+
+use libm::{exp2f, roundf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Seed of the noise generator shared by the dither and jitter.
+const SEED: u32 = 0x0B17_C805;
+
+/// Lowest reduced sample rate in Hz, which keeps the held samples updating.
+const MIN_RATE: f32 = 1.0;
+
+/// Bitcrusher effect, i.e. bit-depth and sample-rate reduction.
+///
+/// The input is held for the period of the reduced sample rate, and each held sample is quantized
+/// to a (fractional) number of bits in the range `-1.0..=1.0`. The quantizer is mid-tread with a
+/// step of `2^(1 - bits)`, so that silence stays silent and both full-scale values are reached:
+/// `bits` bits give `2^bits + 1` levels, one more than an integer format of the same depth.
+///
+/// Jitter randomizes the length of each hold period, and dither adds triangular noise of one step
+/// before quantization, trading the harmonic distortion of the quantizer for a noise floor. Both
+/// channels are held at the same time.
+///
+/// ```
+/// use dspkit::effects::{Bitcrusher, BitcrusherParameters};
+///
+/// static BITCRUSHER: Bitcrusher = Bitcrusher::const_default();
+///
+/// let mut bitcrusher = Bitcrusher::new(BitcrusherParameters {
+///     bits: 2.0,
+///     ..BitcrusherParameters::default()
+/// });
+///
+/// // With 2 bits, every output is one of the 5 levels -1.0, -0.5, 0.0, 0.5 and 1.0.
+/// for n in 0..1_000 {
+///     let x = libm::sinf(0.01 * n as f32);
+///     let out = bitcrusher.tick(&[x, -x]);
+///     assert_eq!(out[0] * 2.0, libm::roundf(out[0] * 2.0));
+/// }
+/// ```
+pub struct Bitcrusher {
+    parameters: BitcrusherParameters,
+    sample_rate: usize,
+    steps: f32,
+    increment: f32,
+    phase: f32,
+    period: f32,
+    held: Stereo<f32>,
+    noise: WhiteNoise,
+}
+
+/// Parameters to the bitcrusher effect.
+#[derive(Clone, Copy)]
+pub struct BitcrusherParameters {
+    /// Bit depth of the quantizer, e.g. 8.0. Fractional depths change the step size continuously.
+    /// Must be at least 1.0.
+    pub bits: f32,
+    /// Reduced sample rate in Hz at which the input is held. Rates at or above the sample rate
+    /// disable the reduction, and rates below 1 Hz are raised to 1 Hz.
+    pub rate: f32,
+    /// Random variation of each hold period. 0.0 = regular to 1.0 = +/- half a period.
+    pub jitter: f32,
+    /// Add triangular dither of one quantization step before quantizing.
+    pub dither: bool,
+    /// Mix of the crushed signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+impl Bitcrusher {
+    /// Construct a bitcrusher effect with the given initial parameters.
+    pub fn new(parameters: BitcrusherParameters) -> Self {
+        let mut bitcrusher = Self::const_default();
+        bitcrusher.parameters = parameters;
+        bitcrusher.prepare(INITIAL_SAMPLE_RATE);
+        bitcrusher
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The quantizer and hold
+    /// period are computed in [`Bitcrusher::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: BitcrusherParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            steps: 1.0,
+            increment: 1.0,
+            phase: 1.0,
+            period: 1.0,
+            held: [0.0; 2],
+            noise: WhiteNoise::new(SEED),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.set_bits(self.parameters.bits);
+        self.set_rate(self.parameters.rate);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.phase += self.increment;
+        if self.phase >= self.period {
+            self.phase -= self.period;
+            self.period = 1.0 + 0.5 * self.parameters.jitter * self.noise.tick();
+            self.held = [self.quantize(input[0]), self.quantize(input[1])];
+        }
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (self.held[0] - input[0]),
+            input[1] + mix * (self.held[1] - input[1]),
+        ]
+    }
+
+    #[inline(always)]
+    fn quantize(&mut self, input: f32) -> f32 {
+        let dither = if self.parameters.dither {
+            self.noise.next_unipolar() - self.noise.next_unipolar()
+        } else {
+            0.0
+        };
+        (roundf(input * self.steps + dither) / self.steps).clamp(-1.0, 1.0)
+    }
+
+    pub fn set_bits(&mut self, val: f32) {
+        self.parameters.bits = val;
+        self.steps = exp2f(val.max(1.0) - 1.0);
+    }
+
+    /// Set the reduced sample rate in Hz, raised to at least 1 Hz so the output never freezes.
+    ///
+    /// ```
+    /// use dspkit::effects::Bitcrusher;
+    ///
+    /// let mut bitcrusher = Bitcrusher::default();
+    /// bitcrusher.set_rate(0.0);
+    ///
+    /// // The first sample is held for a second, after which the input is sampled again.
+    /// let mut out = bitcrusher.tick(&[0.0, 0.0]);
+    /// for _ in 0..48_000 {
+    ///     out = bitcrusher.tick(&[0.5, 0.5]);
+    /// }
+    /// assert_eq!(out, [0.5, 0.5]);
+    /// ```
+    pub fn set_rate(&mut self, val: f32) {
+        self.parameters.rate = val;
+        self.increment = (val.max(MIN_RATE) / self.sample_rate as f32).min(1.0);
+    }
+
+    pub fn set_jitter(&mut self, val: f32) {
+        self.parameters.jitter = val.clamp(0.0, 1.0);
+    }
+
+    pub fn set_dither(&mut self, val: bool) {
+        self.parameters.dither = val;
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Reset the bitcrusher by releasing the held samples and restarting the noise.
+    pub fn reset(&mut self) -> &mut Self {
+        self.phase = 1.0;
+        self.period = 1.0;
+        self.held = [0.0; 2];
+        self.noise.reseed(SEED);
+        self
+    }
+}
+
+impl BitcrusherParameters {
+    pub const fn const_default() -> Self {
+        BitcrusherParameters {
+            bits: 8.0,
+            rate: 11_025.0,
+            jitter: 0.0,
+            dither: false,
+            mix: 1.0,
+        }
+    }
+}
+
+impl Default for BitcrusherParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl Default for Bitcrusher {
+    fn default() -> Self {
+        Self::new(BitcrusherParameters::const_default())
+    }
+}
+
+impl AudioNode<Stereo<f32>, Stereo<f32>> for Bitcrusher {
+    fn prepare(&mut self, sample_rate: usize) {
+        Bitcrusher::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Bitcrusher::tick(self, input)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::components::Convolver;
+use crate::{AudioNode, Stereo, db_to_gain};
+
+/// Convolution reverb with impulse responses of up to `B * P` samples, convolved in `P`
+/// partitions of `B` samples by a [`Convolver`] per channel.
+///
+/// The impulse responses are recordings of real spaces or devices, provided by the caller as
+/// static slices at the sample rate of the processing. Mono impulse responses can be used by
+/// passing the same slice for both channels.
+///
+/// Unless in the zero-latency mode, the reverb is delayed by `B` samples and the dry signal is
+/// delayed to match, which is reported by [`ConvolutionReverb::latency`].
+///
+/// ```
+/// use dspkit::effects::{ConvolutionReverb, ConvolutionReverbParameters};
+///
+/// static REVERB: ConvolutionReverb<256, 32> = ConvolutionReverb::const_default();
+/// static IMPULSE_RESPONSE: [f32; 100] = {
+///     let mut impulse_response = [0.0; 100];
+///     let mut n = 0;
+///     while n < 100 {
+///         impulse_response[n] = 1.0 / (n + 1) as f32;
+///         n += 1;
+///     }
+///     impulse_response
+/// };
+///
+/// let input = |n: usize| libm::sinf(0.1 * n as f32);
+/// let direct = |n: usize| -> f32 {
+///     let taps = IMPULSE_RESPONSE.iter().take(n + 1).enumerate();
+///     taps.map(|(k, h)| h * input(n - k)).sum()
+/// };
+///
+/// for zero_latency in [false, true] {
+///     let mut reverb = ConvolutionReverb::<32, 4>::new(ConvolutionReverbParameters {
+///         impulse_response: [&IMPULSE_RESPONSE, &IMPULSE_RESPONSE],
+///         zero_latency,
+///         gain: 0.0,
+///         mix: 1.0,
+///     });
+///
+///     // The reverb is the convolution of the input, delayed by the latency.
+///     let latency = reverb.latency();
+///     assert_eq!(latency, if zero_latency { 0 } else { 32 });
+///     for n in 0..400 {
+///         let output = reverb.tick(&[input(n), 0.0]);
+///         if n >= latency {
+///             assert!((output[0] - direct(n - latency)).abs() < 1e-4);
+///             assert!(output[1].abs() < 1e-6);
+///         }
+///     }
+///
+///     // The dry signal is delayed by the same latency.
+///     reverb.reset().set_mix(0.0);
+///     for n in 0..100 {
+///         let output = reverb.tick(&[input(n), input(n)]);
+///         if n >= latency {
+///             assert!((output[0] - input(n - latency)).abs() < 1e-6);
+///         }
+///     }
+/// }
+/// ```
+pub struct ConvolutionReverb<const B: usize, const P: usize> {
+    parameters: ConvolutionReverbParameters,
+    gain: f32,
+    convolvers: [Convolver<B, P>; 2],
+    dry: [Stereo<f32>; B],
+    dry_index: usize,
+}
+
+/// Parameters to the convolution reverb effect.
+#[derive(Clone, Copy)]
+pub struct ConvolutionReverbParameters {
+    /// Impulse responses of the left and right channels.
+    pub impulse_response: Stereo<&'static [f32]>,
+    /// Convolve the start of the impulse responses directly, removing the latency of the
+    /// convolution at the cost of more processing per sample.
+    pub zero_latency: bool,
+    /// Gain in decibels applied to the reverb.
+    pub gain: f32,
+    /// Mix of the reverb signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+impl<const B: usize, const P: usize> ConvolutionReverb<B, P> {
+    /// Construct a convolution reverb effect with the given initial parameters, transforming the
+    /// impulse responses.
+    pub fn new(parameters: ConvolutionReverbParameters) -> Self {
+        let mut reverb = Self::const_default();
+        reverb.parameters = parameters;
+        reverb.set_gain(parameters.gain);
+        reverb.set_zero_latency(parameters.zero_latency);
+        reverb.set_impulse_response(parameters.impulse_response);
+        reverb
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The impulse responses are
+    /// empty, so the reverb is silent until [`ConvolutionReverb::set_impulse_response`] is called.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: ConvolutionReverbParameters::const_default(),
+            gain: 1.0,
+            convolvers: [Convolver::const_default(), Convolver::const_default()],
+            dry: [[0.0; 2]; B],
+            dry_index: 0,
+        }
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let wet = [
+            self.gain * self.convolvers[0].tick(input[0]),
+            self.gain * self.convolvers[1].tick(input[1]),
+        ];
+
+        let dry = if self.parameters.zero_latency {
+            *input
+        } else {
+            let dry = self.dry[self.dry_index];
+            self.dry[self.dry_index] = *input;
+            self.dry_index = (self.dry_index + 1) % B;
+            dry
+        };
+
+        let mix = self.parameters.mix;
+        [
+            dry[0] + mix * (wet[0] - dry[0]),
+            dry[1] + mix * (wet[1] - dry[1]),
+        ]
+    }
+
+    /// Convolve with new impulse responses, clearing the current reverb tail.
+    pub fn set_impulse_response(&mut self, val: Stereo<&'static [f32]>) {
+        self.parameters.impulse_response = val;
+        for (convolver, impulse_response) in self.convolvers.iter_mut().zip(val) {
+            convolver.set_impulse_response(impulse_response);
+        }
+    }
+
+    /// Switch the zero-latency mode, clearing the current reverb tail.
+    pub fn set_zero_latency(&mut self, val: bool) {
+        self.parameters.zero_latency = val;
+        for convolver in self.convolvers.iter_mut() {
+            convolver.set_zero_latency(val);
+        }
+        self.dry = [[0.0; 2]; B];
+        self.dry_index = 0;
+    }
+
+    pub fn set_gain(&mut self, val: f32) {
+        self.parameters.gain = val;
+        self.gain = db_to_gain(val);
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Delay in samples of the output, which is `B` unless in the zero-latency mode.
+    pub fn latency(&self) -> usize {
+        self.convolvers[0].latency()
+    }
+
+    /// Reset the reverb by clearing the convolvers and the delayed dry signal.
+    pub fn reset(&mut self) -> &mut Self {
+        for convolver in self.convolvers.iter_mut() {
+            convolver.reset();
+        }
+        self.dry = [[0.0; 2]; B];
+        self.dry_index = 0;
+        self
+    }
+}
+
+impl ConvolutionReverbParameters {
+    pub const fn const_default() -> Self {
+        ConvolutionReverbParameters {
+            impulse_response: [&[], &[]],
+            zero_latency: false,
+            gain: 0.0,
+            mix: 0.3,
+        }
+    }
+}
+
+impl Default for ConvolutionReverbParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const B: usize, const P: usize> Default for ConvolutionReverb<B, P> {
+    fn default() -> Self {
+        Self::new(ConvolutionReverbParameters::const_default())
+    }
+}
+
+impl<const B: usize, const P: usize> AudioNode<Stereo<f32>, Stereo<f32>>
+    for ConvolutionReverb<B, P>
+{
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        ConvolutionReverb::tick(self, input)
+    }
+
+    fn latency(&self) -> usize {
+        ConvolutionReverb::latency(self)
+    }
+}
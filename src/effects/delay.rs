@@ -31,11 +31,11 @@ impl<S: PCM, const N: usize> SimpleDelay<S, N> {
     #[inline(always)]
     pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
         let left = input[0] + self.left.peek().into() * self.feedback;
-        self.left.write(S::from(left));
+        self.left.write_f32(left);
         self.left.advance();
 
         let right = input[1] + self.right.peek().into() * self.feedback;
-        self.right.write(S::from(right));
+        self.right.write_f32(right);
         self.right.advance();
 
         [left, right]
@@ -0,0 +1,247 @@
+mod adaa;
+mod curve;
+
+use crate::components::{Biquad, DcBlock};
+use crate::{AudioNode, Stereo, db_to_gain};
+
+use adaa::Adaa;
+
+pub use adaa::Antialiasing;
+pub use curve::DistortionCurve;
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Quality factor of the tone filter (Butterworth).
+const TONE_Q: f32 = 0.707;
+
+/// Waveshaping distortion with selectable transfer curves.
+///
+/// The input is amplified by the drive, shaped by the transfer curve, and then filtered by a
+/// low-pass tone control before the output gain is applied. A DC blocker removes the offset
+/// introduced by asymmetric curves. Optionally, the aliasing of the curve is suppressed with
+/// antiderivative antialiasing, see [`Antialiasing`].
+///
+/// ```
+/// use dspkit::effects::Distortion;
+///
+/// static DISTORTION: Distortion = Distortion::const_default();
+/// ```
+pub struct Distortion {
+    parameters: DistortionParameters,
+    sample_rate: usize,
+    drive: f32,
+    output: f32,
+    tone_l: Biquad,
+    tone_r: Biquad,
+    dc_l: DcBlock<f32>,
+    dc_r: DcBlock<f32>,
+    adaa_l: Adaa,
+    adaa_r: Adaa,
+}
+
+/// Parameters to the distortion effect.
+#[derive(Clone, Copy)]
+pub struct DistortionParameters {
+    /// Transfer curve of the waveshaper.
+    pub curve: DistortionCurve,
+    /// Gain in dB applied before the waveshaper.
+    pub drive: f32,
+    /// Gain in dB applied after the waveshaper.
+    pub output: f32,
+    /// Cutoff in Hz of the low-pass tone filter after the waveshaper.
+    pub tone: f32,
+    /// Mix of the distorted signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+    /// Antialiasing applied to the waveshaper.
+    pub antialiasing: Antialiasing,
+}
+
+impl Distortion {
+    /// Construct a distortion effect with the given initial parameters.
+    ///
+    /// ```
+    /// use dspkit::effects::{Antialiasing, Distortion, DistortionCurve, DistortionParameters};
+    ///
+    /// let distortion = Distortion::new(DistortionParameters {
+    ///     curve: DistortionCurve::Tube,
+    ///     drive: 18.0,
+    ///     output: -6.0,
+    ///     tone: 6_000.0,
+    ///     mix: 1.0,
+    ///     antialiasing: Antialiasing::FirstOrder,
+    /// });
+    /// ```
+    pub fn new(parameters: DistortionParameters) -> Self {
+        let mut distortion = Self::const_default();
+        distortion.parameters = parameters;
+        distortion.prepare(INITIAL_SAMPLE_RATE);
+        distortion
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The gains and tone filter
+    /// are computed in [`Distortion::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: DistortionParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            drive: 1.0,
+            output: 1.0,
+            tone_l: Biquad::const_default(),
+            tone_r: Biquad::const_default(),
+            dc_l: DcBlock::new(INITIAL_SAMPLE_RATE),
+            dc_r: DcBlock::new(INITIAL_SAMPLE_RATE),
+            adaa_l: Adaa::const_default(),
+            adaa_r: Adaa::const_default(),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.dc_l.prepare(sample_rate);
+        self.dc_r.prepare(sample_rate);
+        self.set_drive(self.parameters.drive);
+        self.set_output(self.parameters.output);
+        self.set_tone(self.parameters.tone);
+        self.adaa_l.refresh(&self.parameters.curve);
+        self.adaa_r.refresh(&self.parameters.curve);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let curve = self.parameters.curve;
+        let antialiasing = self.parameters.antialiasing;
+
+        let wet_l = self
+            .adaa_l
+            .tick(&curve, antialiasing, input[0] * self.drive);
+        let wet_l = self.tone_l.tick(&self.dc_l.tick(&wet_l)) * self.output;
+
+        let wet_r = self
+            .adaa_r
+            .tick(&curve, antialiasing, input[1] * self.drive);
+        let wet_r = self.tone_r.tick(&self.dc_r.tick(&wet_r)) * self.output;
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (wet_l - input[0]),
+            input[1] + mix * (wet_r - input[1]),
+        ]
+    }
+
+    pub fn set_curve(&mut self, val: DistortionCurve) {
+        self.parameters.curve = val;
+        self.adaa_l.refresh(&val);
+        self.adaa_r.refresh(&val);
+    }
+
+    pub fn set_drive(&mut self, val: f32) {
+        self.parameters.drive = val;
+        self.drive = db_to_gain(val);
+    }
+
+    pub fn set_output(&mut self, val: f32) {
+        self.parameters.output = val;
+        self.output = db_to_gain(val);
+    }
+
+    pub fn set_tone(&mut self, val: f32) {
+        self.parameters.tone = val;
+        self.tone_l.set_lowpass(val, TONE_Q, self.sample_rate);
+        self.tone_r.set_lowpass(val, TONE_Q, self.sample_rate);
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    pub fn set_antialiasing(&mut self, val: Antialiasing) {
+        self.parameters.antialiasing = val;
+        self.adaa_l.refresh(&self.parameters.curve);
+        self.adaa_r.refresh(&self.parameters.curve);
+    }
+
+    /// Reset the distortion by clearing the internal filters and antialiasing state.
+    ///
+    /// ```
+    /// use dspkit::effects::{Antialiasing, Distortion, DistortionCurve, DistortionParameters};
+    ///
+    /// let curves = [
+    ///     DistortionCurve::SoftClip,
+    ///     DistortionCurve::HardClip,
+    ///     DistortionCurve::Cubic,
+    ///     DistortionCurve::Arctan,
+    ///     DistortionCurve::Tube,
+    ///     DistortionCurve::Foldback,
+    ///     DistortionCurve::Bitcrush { bits: 8.0 },
+    /// ];
+    /// let distortion = |curve, antialiasing| {
+    ///     Distortion::new(DistortionParameters {
+    ///         curve,
+    ///         drive: 0.0,
+    ///         antialiasing,
+    ///         ..DistortionParameters::default()
+    ///     })
+    /// };
+    ///
+    /// // The first outputs with antialiasing follow the curve, both after construction and after
+    /// // a reset.
+    /// for curve in curves {
+    ///     for antialiasing in [Antialiasing::FirstOrder, Antialiasing::SecondOrder] {
+    ///         let mut reference = distortion(curve, Antialiasing::Off);
+    ///         let mut adaa = distortion(curve, antialiasing);
+    ///         for _ in 0..2 {
+    ///             for _ in 0..8 {
+    ///                 let expected = reference.tick(&[0.01, 0.01]);
+    ///                 let out = adaa.tick(&[0.01, 0.01]);
+    ///                 assert!((out[0] - expected[0]).abs() < 0.01);
+    ///             }
+    ///             reference.reset();
+    ///             adaa.reset();
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn reset(&mut self) -> &mut Self {
+        self.adaa_l.reset(&self.parameters.curve);
+        self.adaa_r.reset(&self.parameters.curve);
+        self.tone_l.reset();
+        self.tone_r.reset();
+        self.dc_l = DcBlock::new(self.sample_rate);
+        self.dc_r = DcBlock::new(self.sample_rate);
+        self
+    }
+}
+
+impl DistortionParameters {
+    pub const fn const_default() -> Self {
+        DistortionParameters {
+            curve: DistortionCurve::SoftClip,
+            drive: 12.0,
+            output: 0.0,
+            tone: 20_000.0,
+            mix: 1.0,
+            antialiasing: Antialiasing::Off,
+        }
+    }
+}
+
+impl Default for DistortionParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self::new(DistortionParameters::const_default())
+    }
+}
+
+impl AudioNode<Stereo<f32>, Stereo<f32>> for Distortion {
+    fn prepare(&mut self, sample_rate: usize) {
+        Distortion::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Distortion::tick(self, input)
+    }
+}
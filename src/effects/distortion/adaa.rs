@@ -0,0 +1,143 @@
+use super::DistortionCurve;
+
+/// Difference between consecutive inputs below which the divided differences are ill-conditioned,
+/// and the curve is instead evaluated at the midpoint.
+const TOLERANCE: f64 = 1e-4;
+
+/// Antiderivative antialiasing applied to the waveshaper of the [`Distortion`](super::Distortion)
+/// effect.
+///
+/// Rather than evaluating the curve at each sample, antiderivative antialiasing evaluates the
+/// average of the curve between consecutive samples through its antiderivatives. This suppresses
+/// the aliasing of the harmonics generated by the curve without the cost of oversampling, but
+/// slightly attenuates high frequencies and delays the distorted signal by half a sample (first
+/// order) or one sample (second order).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Antialiasing {
+    /// Evaluate the curve directly.
+    Off,
+    /// First-order antiderivative antialiasing.
+    FirstOrder,
+    /// Second-order antiderivative antialiasing. Suppresses aliasing further than
+    /// [`Antialiasing::FirstOrder`], at a higher cost per sample.
+    SecondOrder,
+}
+
+/// State of the antiderivative antialiasing for a single channel.
+///
+/// The antiderivatives of the previous input are cached, so [`Adaa::refresh`] must be called
+/// before processing and whenever the curve or the antialiasing order changes.
+pub(super) struct Adaa {
+    /// Previous two inputs.
+    x1: f64,
+    x2: f64,
+    /// First and second antiderivatives at the previous input.
+    ad1_x1: f64,
+    ad2_x1: f64,
+    /// Divided difference of the second antiderivative between the previous two inputs.
+    d1: f64,
+}
+
+impl Adaa {
+    pub(super) const fn const_default() -> Self {
+        Self {
+            x1: 0.0,
+            x2: 0.0,
+            ad1_x1: 0.0,
+            ad2_x1: 0.0,
+            d1: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn tick(
+        &mut self,
+        curve: &DistortionCurve,
+        antialiasing: Antialiasing,
+        input: f32,
+    ) -> f32 {
+        let x = input as f64;
+        let out = match antialiasing {
+            Antialiasing::Off => curve.apply(input),
+            Antialiasing::FirstOrder => self.first_order(curve, x) as f32,
+            Antialiasing::SecondOrder => self.second_order(curve, x) as f32,
+        };
+        self.x2 = self.x1;
+        self.x1 = x;
+        out
+    }
+
+    #[inline(always)]
+    fn first_order(&mut self, curve: &DistortionCurve, x: f64) -> f64 {
+        let ad1 = curve.antiderivative(x);
+        let delta = x - self.x1;
+        let out = if delta.abs() > TOLERANCE {
+            (ad1 - self.ad1_x1) / delta
+        } else {
+            apply(curve, 0.5 * (x + self.x1))
+        };
+        self.ad1_x1 = ad1;
+        out
+    }
+
+    #[inline(always)]
+    fn second_order(&mut self, curve: &DistortionCurve, x: f64) -> f64 {
+        let ad2 = curve.second_antiderivative(x);
+        let d0 = self.divided_difference(curve, x, ad2);
+
+        let delta = x - self.x2;
+        let out = if delta.abs() > TOLERANCE {
+            2.0 * (d0 - self.d1) / delta
+        } else {
+            // The outer difference is ill-conditioned, so average the curve between the midpoint
+            // of the outer inputs and the middle input instead.
+            let mid = 0.5 * (x + self.x2);
+            let delta = mid - self.x1;
+            if delta.abs() > TOLERANCE {
+                let ad2_mid = curve.second_antiderivative(mid);
+                2.0 / delta * (curve.antiderivative(mid) + (self.ad2_x1 - ad2_mid) / delta)
+            } else {
+                apply(curve, 0.5 * (mid + self.x1))
+            }
+        };
+
+        self.d1 = d0;
+        self.ad2_x1 = ad2;
+        out
+    }
+
+    /// Divided difference of the second antiderivative between `x` and the previous input.
+    #[inline(always)]
+    fn divided_difference(&self, curve: &DistortionCurve, x: f64, ad2: f64) -> f64 {
+        let delta = x - self.x1;
+        if delta.abs() > TOLERANCE {
+            (ad2 - self.ad2_x1) / delta
+        } else {
+            curve.antiderivative(0.5 * (x + self.x1))
+        }
+    }
+
+    /// Recompute the cached antiderivatives from the previous inputs.
+    pub(super) fn refresh(&mut self, curve: &DistortionCurve) {
+        self.ad1_x1 = curve.antiderivative(self.x1);
+        self.ad2_x1 = curve.second_antiderivative(self.x1);
+        let ad2_x2 = curve.second_antiderivative(self.x2);
+        let delta = self.x1 - self.x2;
+        self.d1 = if delta.abs() > TOLERANCE {
+            (self.ad2_x1 - ad2_x2) / delta
+        } else {
+            curve.antiderivative(0.5 * (self.x1 + self.x2))
+        };
+    }
+
+    /// Clear the previous inputs, and recompute the cached antiderivatives for the curve.
+    pub(super) fn reset(&mut self, curve: &DistortionCurve) {
+        *self = Self::const_default();
+        self.refresh(curve);
+    }
+}
+
+#[inline(always)]
+fn apply(curve: &DistortionCurve, x: f64) -> f64 {
+    curve.apply(x as f32) as f64
+}
@@ -0,0 +1,197 @@
+use crate::PCM;
+
+use core::f32::consts::FRAC_2_PI;
+use core::f64::consts::{FRAC_2_PI as FRAC_2_PI_F64, LN_2, PI};
+use libm::{atan, atanf, exp, exp2, exp2f, floor, floorf, log, log1p, round, roundf, tanh, tanhf};
+
+/// Bias of the tube curve, shifting the operating point to clip positive peaks harder than
+/// negative peaks.
+const TUBE_BIAS: f32 = 0.3;
+
+/// Transfer curve of the [`Distortion`](super::Distortion) effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistortionCurve {
+    /// Smooth saturation with `tanh`.
+    SoftClip,
+    /// Clamp to the range `-1.0..=1.0`.
+    HardClip,
+    /// Cubic polynomial soft clip, reaching the limits of `-1.0..=1.0` at unity input.
+    Cubic,
+    /// Gentle saturation with `atan`, scaled to the range `-1.0..=1.0`.
+    Arctan,
+    /// Asymmetric saturation emulating a biased tube stage, adding even harmonics.
+    Tube,
+    /// Fold the signal back on itself whenever it exceeds the range `-1.0..=1.0`.
+    Foldback,
+    /// Quantize to a (fractional) number of bits, with a mid-tread step of `2^(1 - bits)`. Within
+    /// `-1.0..=1.0`, `bits` bits give `2^bits + 1` levels.
+    Bitcrush {
+        /// Bit depth of the quantizer, e.g. 8.0. Must be at least 1.0.
+        bits: f32,
+    },
+}
+
+impl DistortionCurve {
+    /// Apply the transfer curve to a single sample.
+    ///
+    /// ```
+    /// use dspkit::effects::DistortionCurve;
+    ///
+    /// assert_eq!(DistortionCurve::HardClip.apply(1.5), 1.0);
+    /// assert_eq!(DistortionCurve::Foldback.apply(1.5), 0.5);
+    /// assert_eq!(DistortionCurve::Bitcrush { bits: 2.0 }.apply(0.3), 0.5);
+    /// ```
+    #[inline(always)]
+    pub fn apply(&self, x: f32) -> f32 {
+        match self {
+            Self::SoftClip => tanhf(x),
+            Self::HardClip => x.constrain(),
+            Self::Cubic => {
+                let x = x.constrain();
+                1.5 * x - 0.5 * x * x * x
+            }
+            Self::Arctan => FRAC_2_PI * atanf(x),
+            Self::Tube => tanhf(x + TUBE_BIAS) - tanhf(TUBE_BIAS),
+            Self::Foldback => {
+                // Triangle wave with a period of 4.0, matching the identity within -1.0..=1.0.
+                let t = x + 1.0;
+                let t = t - 4.0 * floorf(t * 0.25);
+                if t < 2.0 { t - 1.0 } else { 3.0 - t }
+            }
+            Self::Bitcrush { bits } => {
+                let steps = exp2f(bits.max(1.0) - 1.0);
+                roundf(x * steps) / steps
+            }
+        }
+    }
+
+    /// First antiderivative of the transfer curve, used for antiderivative antialiasing.
+    ///
+    /// Evaluated in double precision, since antialiasing divides differences of nearly equal
+    /// antiderivative values.
+    pub fn antiderivative(&self, x: f64) -> f64 {
+        match self {
+            Self::SoftClip => log_cosh(x),
+            Self::HardClip => {
+                let a = x.abs();
+                if a <= 1.0 { 0.5 * x * x } else { a - 0.5 }
+            }
+            Self::Cubic => {
+                let a = x.abs();
+                if a <= 1.0 {
+                    0.75 * x * x - 0.125 * x * x * x * x
+                } else {
+                    a - 0.375
+                }
+            }
+            Self::Arctan => FRAC_2_PI_F64 * (x * atan(x) - 0.5 * log1p(x * x)),
+            Self::Tube => {
+                let bias = TUBE_BIAS as f64;
+                log_cosh(x + bias) - x * tanh(bias)
+            }
+            Self::Foldback => {
+                let t = x + 1.0;
+                let t = t - 4.0 * floor(t * 0.25);
+                if t < 2.0 {
+                    0.5 * (t - 1.0) * (t - 1.0)
+                } else {
+                    1.0 - 0.5 * (t - 3.0) * (t - 3.0)
+                }
+            }
+            Self::Bitcrush { bits } => {
+                let step = exp2(1.0 - bits.max(1.0) as f64);
+                let y = x / step;
+                let m = round(y);
+                step * step * (m * y - 0.5 * m * m)
+            }
+        }
+    }
+
+    /// Second antiderivative of the transfer curve, used for antiderivative antialiasing.
+    ///
+    /// Evaluated in double precision, since antialiasing divides differences of nearly equal
+    /// antiderivative values.
+    pub fn second_antiderivative(&self, x: f64) -> f64 {
+        match self {
+            Self::SoftClip => integral_log_cosh(x),
+            Self::HardClip => {
+                let a = x.abs();
+                if a <= 1.0 {
+                    x * x * x / 6.0
+                } else {
+                    x.signum() * (0.5 * a * a - 0.5 * a + 1.0 / 6.0)
+                }
+            }
+            Self::Cubic => {
+                let a = x.abs();
+                if a <= 1.0 {
+                    0.25 * x * x * x - x * x * x * x * x / 40.0
+                } else {
+                    x.signum() * (0.5 * a * a - 0.375 * a + 0.1)
+                }
+            }
+            Self::Arctan => {
+                FRAC_2_PI_F64 * (0.5 * (x * x - 1.0) * atan(x) + 0.5 * x - 0.5 * x * log1p(x * x))
+            }
+            Self::Tube => {
+                let bias = TUBE_BIAS as f64;
+                integral_log_cosh(x + bias) - 0.5 * x * x * tanh(bias)
+            }
+            Self::Foldback => {
+                // Each period of the folded antiderivative integrates to 2.0.
+                let periods = floor((x + 1.0) * 0.25);
+                let t = x + 1.0 - 4.0 * periods;
+                let partial = if t < 2.0 {
+                    ((t - 1.0) * (t - 1.0) * (t - 1.0) + 1.0) / 6.0
+                } else {
+                    1.0 / 3.0 + (t - 2.0) - ((t - 3.0) * (t - 3.0) * (t - 3.0) + 1.0) / 6.0
+                };
+                2.0 * periods + partial
+            }
+            Self::Bitcrush { bits } => {
+                let step = exp2(1.0 - bits.max(1.0) as f64);
+                let y = (x / step).abs();
+                let m = round(y);
+                // Sum of the whole steps below the current one, plus the partial current step.
+                let whole = (m - 1.0) * m * (2.0 * m - 1.0) / 12.0;
+                let partial =
+                    0.5 * m * (y * y - (m - 0.5) * (m - 0.5)) - 0.5 * m * m * (y - m + 0.5);
+                let partial = if m == 0.0 { 0.0 } else { partial };
+                x.signum() * step * step * step * (whole + partial)
+            }
+        }
+    }
+}
+
+/// Numerically stable `ln(cosh(x))`, the antiderivative of `tanh`.
+#[inline(always)]
+fn log_cosh(x: f64) -> f64 {
+    let a = x.abs();
+    a + log1p(exp(-2.0 * a)) - LN_2
+}
+
+/// Antiderivative of `ln(cosh(x))` which is zero at the origin, expressed with the dilogarithm.
+#[inline(always)]
+fn integral_log_cosh(x: f64) -> f64 {
+    let a = x.abs();
+    let integral = 0.5 * a * a - a * LN_2 + 0.5 * dilog(-exp(-2.0 * a)) + PI * PI / 24.0;
+    x.signum() * integral
+}
+
+/// Dilogarithm `Li2(z)` for `z` in the range `-1.0..=0.0`.
+///
+/// Uses the Landen identity `Li2(z) = -Li2(z / (z - 1)) - ln(1 - z)^2 / 2`, which maps the input
+/// into `0.0..=0.5` where the power series converges quickly.
+fn dilog(z: f64) -> f64 {
+    let w = z / (z - 1.0);
+    let mut sum = 0.0;
+    let mut power = w;
+    let mut k = 1.0;
+    while power > 1e-17 {
+        sum += power / (k * k);
+        power *= w;
+        k += 1.0;
+    }
+    let ln = log(1.0 - z);
+    -sum - 0.5 * ln * ln
+}
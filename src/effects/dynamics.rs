@@ -0,0 +1,9 @@
+mod compressor;
+mod gate;
+mod limiter;
+mod transient_shaper;
+
+pub use compressor::*;
+pub use gate::*;
+pub use limiter::*;
+pub use transient_shaper::*;
@@ -0,0 +1,418 @@
+use crate::components::{Biquad, EnvelopeFollower, RmsDetector};
+use crate::{AudioNode, SidechainNode, Stereo, db_to_gain, gain_to_db};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Averaging time of the RMS detector in milliseconds.
+const RMS_WINDOW_MS: f32 = 10.0;
+
+/// Capacity of the RMS detector in samples, i.e. the window at sample rates up to 192 kHz.
+const RMS_CAPACITY: usize = 1_920;
+
+/// Quality factor of the sidechain high-pass filter (Butterworth).
+const SIDECHAIN_HPF_Q: f32 = 0.707;
+
+/// Dynamic range compressor with a soft knee.
+///
+/// The detected level is mapped through a static gain curve in decibels, and the resulting gain
+/// reduction is smoothed with separate attack and release times. The detector either listens to
+/// the input (feed-forward) or to the previous output (feedback), the latter giving the smoother
+/// response of vintage designs.
+///
+/// An external key signal can drive the detector instead through [`Compressor::tick_sidechain`],
+/// e.g. for ducking. The key is always high-pass filtered when
+/// [`CompressorParameters::sidechain_hpf`] is set, so low frequencies do not dominate the gain
+/// reduction.
+///
+/// ```
+/// use dspkit::effects::Compressor;
+///
+/// static COMPRESSOR: Compressor = Compressor::const_default();
+/// ```
+pub struct Compressor {
+    parameters: CompressorParameters,
+    sample_rate: usize,
+    makeup: f32,
+    left: CompressorChannel,
+    right: CompressorChannel,
+    meter: f32,
+}
+
+/// Level detection used by dynamics processors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetectionMode {
+    /// Responds to the instantaneous absolute level of the signal.
+    Peak,
+    /// Responds to the average power of the signal over a short window.
+    Rms,
+}
+
+/// Detector topology of the compressor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompressorTopology {
+    /// The detector listens to the input signal.
+    FeedForward,
+    /// The detector listens to the compressed output signal.
+    FeedBack,
+}
+
+/// Parameters to the compressor effect.
+#[derive(Clone, Copy)]
+pub struct CompressorParameters {
+    /// Level in dB above which the signal is compressed.
+    pub threshold: f32,
+    /// Input to output ratio above the threshold, e.g. 4.0 for 4:1. Must be at least 1.0.
+    pub ratio: f32,
+    /// Width of the soft knee in dB, centered on the threshold. 0.0 = hard knee.
+    pub knee: f32,
+    /// Time in milliseconds for the gain reduction to increase.
+    pub attack: f32,
+    /// Time in milliseconds for the gain reduction to recover.
+    pub release: f32,
+    /// Gain in dB applied after compression.
+    pub makeup: f32,
+    /// Add makeup gain compensating for half of the gain reduction of a full scale signal.
+    pub auto_makeup: bool,
+    /// Level detection of the signal.
+    pub detection: DetectionMode,
+    /// Amount of linking between the left and right detectors. 0.0 = independent channels to
+    /// 1.0 = both channels receive the same gain reduction.
+    pub stereo_link: f32,
+    /// Whether the detector listens to the input or output.
+    pub topology: CompressorTopology,
+    /// Cutoff in Hz of the high-pass filter applied to the detector signal. 0.0 = disabled.
+    pub sidechain_hpf: f32,
+}
+
+impl Compressor {
+    /// Construct a compressor effect with the given initial parameters. The parameters are
+    /// validated in the same way as by the setters.
+    ///
+    /// ```
+    /// use dspkit::effects::{Compressor, CompressorParameters, CompressorTopology, DetectionMode};
+    ///
+    /// let compressor = Compressor::new(CompressorParameters {
+    ///     threshold: -18.0,
+    ///     ratio: 4.0,
+    ///     knee: 6.0,
+    ///     attack: 10.0,
+    ///     release: 100.0,
+    ///     makeup: 0.0,
+    ///     auto_makeup: true,
+    ///     detection: DetectionMode::Peak,
+    ///     stereo_link: 1.0,
+    ///     topology: CompressorTopology::FeedForward,
+    ///     sidechain_hpf: 0.0,
+    /// });
+    ///
+    /// // A ratio below 1.0 is raised to 1.0 rather than expanding loud signals.
+    /// let mut compressor = Compressor::new(CompressorParameters {
+    ///     threshold: -20.0,
+    ///     ratio: 0.5,
+    ///     auto_makeup: false,
+    ///     ..CompressorParameters::default()
+    /// });
+    /// let mut out = [0.0; 2];
+    /// for _ in 0..48_000 {
+    ///     out = compressor.tick(&[0.5, 0.5]);
+    /// }
+    /// assert!((out[0] - 0.5).abs() < 1e-3);
+    /// ```
+    pub fn new(parameters: CompressorParameters) -> Self {
+        let mut compressor = Self::const_default();
+        compressor.parameters = parameters;
+        compressor.prepare(INITIAL_SAMPLE_RATE);
+        compressor
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The attack and release
+    /// are computed in [`Compressor::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: CompressorParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            makeup: 1.0,
+            left: CompressorChannel::const_default(),
+            right: CompressorChannel::const_default(),
+            meter: 0.0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        for channel in [&mut self.left, &mut self.right] {
+            channel.rms.set_window(RMS_WINDOW_MS, sample_rate);
+        }
+        self.set_attack(self.parameters.attack);
+        self.set_release(self.parameters.release);
+        self.set_sidechain_hpf(self.parameters.sidechain_hpf);
+        self.set_stereo_link(self.parameters.stereo_link);
+        self.set_ratio(self.parameters.ratio);
+        self.set_knee(self.parameters.knee);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let key = match self.parameters.topology {
+            CompressorTopology::FeedForward => *input,
+            CompressorTopology::FeedBack => [self.left.feedback, self.right.feedback],
+        };
+        self.process(input, &key)
+    }
+
+    /// Process a frame with the detector listening to an external key frame, regardless of the
+    /// topology.
+    ///
+    /// ```
+    /// use dspkit::effects::Compressor;
+    ///
+    /// let mut compressor = Compressor::default();
+    ///
+    /// // A loud key signal ducks a quiet input.
+    /// let mut out = [0.0; 2];
+    /// for _ in 0..4_800 {
+    ///     out = compressor.tick_sidechain(&[0.1, 0.1], &[1.0, 1.0]);
+    /// }
+    ///
+    /// assert!(out[0] < 0.05);
+    /// ```
+    pub fn tick_sidechain(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        self.process(input, key)
+    }
+
+    #[inline(always)]
+    fn process(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        let detection = self.parameters.detection;
+        let mut level_l = self.left.detect(key[0], detection);
+        let mut level_r = self.right.detect(key[1], detection);
+
+        let linked = level_l.max(level_r);
+        level_l += self.parameters.stereo_link * (linked - level_l);
+        level_r += self.parameters.stereo_link * (linked - level_r);
+
+        let reduction_l = self.left.reduction.tick(self.static_reduction(level_l));
+        let reduction_r = self.right.reduction.tick(self.static_reduction(level_r));
+        self.meter = reduction_l.max(reduction_r);
+
+        let out_l = input[0] * db_to_gain(-reduction_l);
+        let out_r = input[1] * db_to_gain(-reduction_r);
+        self.left.feedback = out_l;
+        self.right.feedback = out_r;
+
+        [out_l * self.makeup, out_r * self.makeup]
+    }
+
+    /// Current gain reduction in dB, as a positive value. Useful for metering.
+    ///
+    /// ```
+    /// use dspkit::effects::Compressor;
+    ///
+    /// let mut compressor = Compressor::default();
+    /// compressor.set_threshold(-20.0);
+    /// compressor.set_ratio(4.0);
+    ///
+    /// for _ in 0..48_000 {
+    ///     compressor.tick(&[0.5, -0.5]);
+    /// }
+    ///
+    /// assert!(compressor.gain_reduction() > 5.0);
+    /// ```
+    pub fn gain_reduction(&self) -> f32 {
+        self.meter
+    }
+
+    pub fn set_threshold(&mut self, val: f32) {
+        self.parameters.threshold = val;
+        self.update_makeup();
+    }
+
+    pub fn set_ratio(&mut self, val: f32) {
+        self.parameters.ratio = val.max(1.0);
+        self.update_makeup();
+    }
+
+    pub fn set_knee(&mut self, val: f32) {
+        self.parameters.knee = val.max(0.0);
+        self.update_makeup();
+    }
+
+    pub fn set_attack(&mut self, val: f32) {
+        self.parameters.attack = val;
+        self.left.reduction.set_attack(val, self.sample_rate);
+        self.right.reduction.set_attack(val, self.sample_rate);
+    }
+
+    pub fn set_release(&mut self, val: f32) {
+        self.parameters.release = val;
+        self.left.reduction.set_release(val, self.sample_rate);
+        self.right.reduction.set_release(val, self.sample_rate);
+    }
+
+    pub fn set_makeup(&mut self, val: f32) {
+        self.parameters.makeup = val;
+        self.update_makeup();
+    }
+
+    pub fn set_auto_makeup(&mut self, val: bool) {
+        self.parameters.auto_makeup = val;
+        self.update_makeup();
+    }
+
+    /// Set the level detection of the signal.
+    ///
+    /// ```
+    /// use dspkit::effects::{Compressor, DetectionMode};
+    ///
+    /// let mut compressor = Compressor::default();
+    /// compressor.set_detection(DetectionMode::Rms);
+    ///
+    /// // A square wave at -6 dB RMS is 6 dB above the threshold, and reduced by 6 dB * (1 - 1/4).
+    /// for n in 0..48_000 {
+    ///     let x = if n % 2 == 0 { 0.5 } else { -0.5 };
+    ///     compressor.tick(&[x, x]);
+    /// }
+    /// assert!((compressor.gain_reduction() - 4.5).abs() < 0.1);
+    /// ```
+    pub fn set_detection(&mut self, val: DetectionMode) {
+        self.parameters.detection = val;
+    }
+
+    pub fn set_stereo_link(&mut self, val: f32) {
+        self.parameters.stereo_link = val.clamp(0.0, 1.0);
+    }
+
+    pub fn set_topology(&mut self, val: CompressorTopology) {
+        self.parameters.topology = val;
+    }
+
+    pub fn set_sidechain_hpf(&mut self, val: f32) {
+        self.parameters.sidechain_hpf = val;
+        for channel in [&mut self.left, &mut self.right] {
+            if val > 0.0 {
+                channel
+                    .hpf
+                    .set_highpass(val, SIDECHAIN_HPF_Q, self.sample_rate);
+            } else {
+                channel.hpf.set_bypass();
+            }
+        }
+    }
+
+    /// Reset the compressor by clearing the detectors and gain reduction.
+    pub fn reset(&mut self) -> &mut Self {
+        self.left.reset();
+        self.right.reset();
+        self.meter = 0.0;
+        self
+    }
+
+    /// Gain reduction in dB for a detected level in dB, following the soft knee curve.
+    #[inline(always)]
+    fn static_reduction(&self, level: f32) -> f32 {
+        let slope = 1.0 - 1.0 / self.parameters.ratio;
+        let knee = self.parameters.knee;
+        let over = level - self.parameters.threshold;
+
+        if 2.0 * over <= -knee {
+            0.0
+        } else if 2.0 * over < knee {
+            slope * (over + 0.5 * knee) * (over + 0.5 * knee) / (2.0 * knee)
+        } else {
+            slope * over
+        }
+    }
+
+    fn update_makeup(&mut self) {
+        let mut makeup = self.parameters.makeup;
+        if self.parameters.auto_makeup {
+            makeup += 0.5 * self.static_reduction(0.0);
+        }
+        self.makeup = db_to_gain(makeup);
+    }
+}
+
+/// Detector and gain reduction state for a single channel.
+struct CompressorChannel {
+    hpf: Biquad,
+    rms: RmsDetector<RMS_CAPACITY>,
+    reduction: EnvelopeFollower,
+    feedback: f32,
+}
+
+impl CompressorChannel {
+    const fn const_default() -> Self {
+        Self {
+            hpf: Biquad::const_default(),
+            rms: RmsDetector::const_default(),
+            reduction: EnvelopeFollower::const_default(),
+            feedback: 0.0,
+        }
+    }
+
+    /// Detect the level of the key signal in dB.
+    #[inline(always)]
+    fn detect(&mut self, key: f32, detection: DetectionMode) -> f32 {
+        let key = self.hpf.tick(&key);
+        let level = match detection {
+            DetectionMode::Peak => key.abs(),
+            DetectionMode::Rms => self.rms.tick(&key),
+        };
+        gain_to_db(level)
+    }
+
+    fn reset(&mut self) {
+        self.hpf.reset();
+        self.rms.reset();
+        self.reduction.reset();
+        self.feedback = 0.0;
+    }
+}
+
+impl CompressorParameters {
+    pub const fn const_default() -> Self {
+        CompressorParameters {
+            threshold: -12.0,
+            ratio: 4.0,
+            knee: 6.0,
+            attack: 10.0,
+            release: 100.0,
+            makeup: 0.0,
+            auto_makeup: false,
+            detection: DetectionMode::Peak,
+            stereo_link: 1.0,
+            topology: CompressorTopology::FeedForward,
+            sidechain_hpf: 0.0,
+        }
+    }
+}
+
+impl Default for CompressorParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(CompressorParameters::const_default())
+    }
+}
+
+impl AudioNode<Stereo<f32>, Stereo<f32>> for Compressor {
+    fn prepare(&mut self, sample_rate: usize) {
+        Compressor::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Compressor::tick(self, input)
+    }
+}
+
+impl SidechainNode<Stereo<f32>, Stereo<f32>, Stereo<f32>> for Compressor {
+    fn prepare(&mut self, sample_rate: usize) {
+        Compressor::prepare(self, sample_rate);
+    }
+
+    fn tick_sidechain(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        Compressor::tick_sidechain(self, input, key)
+    }
+}
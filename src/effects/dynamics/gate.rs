@@ -0,0 +1,293 @@
+use crate::components::{Biquad, EnvelopeFollower};
+use crate::{AudioNode, MIN_DB, SidechainNode, Stereo, db_to_gain, gain_to_db};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Release time of the level detector in milliseconds, long enough to bridge the zero crossings
+/// of low frequencies.
+const DETECTOR_RELEASE_MS: f32 = 20.0;
+
+/// Quality factor of the sidechain high-pass filter (Butterworth).
+const SIDECHAIN_HPF_Q: f32 = 0.707;
+
+/// Noise gate and downward expander.
+///
+/// The gate opens once the detected level rises above the open threshold, and only starts to close
+/// once the level has stayed below the (lower) close threshold for the hold time. This hysteresis
+/// prevents chattering around a single threshold. When closed, the signal is attenuated by the
+/// range rather than fully muted. Both channels share the same gain to preserve the stereo image.
+///
+/// ```
+/// use dspkit::effects::Gate;
+///
+/// static GATE: Gate = Gate::const_default();
+/// ```
+pub struct Gate {
+    parameters: GateParameters,
+    sample_rate: usize,
+    hold_samples: usize,
+    hold_counter: usize,
+    open: bool,
+    hpf_l: Biquad,
+    hpf_r: Biquad,
+    detector: EnvelopeFollower,
+    gain: EnvelopeFollower,
+    meter: f32,
+}
+
+/// Behaviour of the gate below the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateMode {
+    /// Attenuate the signal by the full range once the gate closes.
+    Gate,
+    /// Attenuate the signal in proportion to how far the level falls below the open threshold,
+    /// following the expander ratio, down to the range.
+    Expander,
+}
+
+/// Parameters to the gate effect.
+#[derive(Clone, Copy)]
+pub struct GateParameters {
+    /// Level in dB above which the gate opens.
+    pub open_threshold: f32,
+    /// Level in dB below which the gate starts to close. Should not exceed the open threshold.
+    pub close_threshold: f32,
+    /// Time in milliseconds for the gate to open.
+    pub attack: f32,
+    /// Time in milliseconds the gate stays open after the level falls below the close threshold.
+    pub hold: f32,
+    /// Time in milliseconds for the gate to close.
+    pub release: f32,
+    /// Attenuation in dB applied when the gate is closed, e.g. -80.0. 0.0 = no attenuation.
+    pub range: f32,
+    /// Whether to gate or expand below the threshold.
+    pub mode: GateMode,
+    /// Expander ratio, e.g. 2.0 for 1:2 downward expansion. Only used by [`GateMode::Expander`].
+    pub ratio: f32,
+    /// Cutoff in Hz of the high-pass filter applied to the detector signal. 0.0 = disabled.
+    pub sidechain_hpf: f32,
+}
+
+impl Gate {
+    /// Construct a gate effect with the given initial parameters.
+    pub fn new(parameters: GateParameters) -> Self {
+        let mut gate = Self::const_default();
+        gate.parameters = parameters;
+        gate.prepare(INITIAL_SAMPLE_RATE);
+        gate
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The time constants are
+    /// computed in [`Gate::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: GateParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            hold_samples: 0,
+            hold_counter: 0,
+            open: false,
+            hpf_l: Biquad::const_default(),
+            hpf_r: Biquad::const_default(),
+            detector: EnvelopeFollower::const_default(),
+            gain: EnvelopeFollower::const_default(),
+            meter: 0.0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.detector.set_attack(0.0, sample_rate);
+        self.detector.set_release(DETECTOR_RELEASE_MS, sample_rate);
+        self.set_attack(self.parameters.attack);
+        self.set_hold(self.parameters.hold);
+        self.set_release(self.parameters.release);
+        self.set_sidechain_hpf(self.parameters.sidechain_hpf);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.process(input, input)
+    }
+
+    /// Process a frame with the detector listening to an external key frame.
+    pub fn tick_sidechain(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        self.process(input, key)
+    }
+
+    #[inline(always)]
+    fn process(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        let key_l = self.hpf_l.tick(&key[0]);
+        let key_r = self.hpf_r.tick(&key[1]);
+        let level = gain_to_db(self.detector.tick(key_l.abs().max(key_r.abs())));
+
+        let threshold = if self.open {
+            self.parameters.close_threshold
+        } else {
+            self.parameters.open_threshold
+        };
+        if level >= threshold {
+            self.open = true;
+            self.hold_counter = self.hold_samples;
+        } else if self.hold_counter > 0 {
+            self.hold_counter -= 1;
+        } else {
+            self.open = false;
+        }
+
+        let range = self.parameters.range.clamp(MIN_DB, 0.0);
+        let target = match (self.open, self.parameters.mode) {
+            (true, _) => 0.0,
+            (false, GateMode::Gate) => range,
+            (false, GateMode::Expander) => {
+                let under = level - self.parameters.open_threshold;
+                (under * (self.parameters.ratio - 1.0)).clamp(range, 0.0)
+            }
+        };
+
+        let gain = self.gain.tick(db_to_gain(target));
+        self.meter = -gain_to_db(gain);
+
+        [input[0] * gain, input[1] * gain]
+    }
+
+    /// Current gain reduction in dB, as a positive value. Useful for metering.
+    pub fn gain_reduction(&self) -> f32 {
+        self.meter
+    }
+
+    /// Whether the gate is currently open (including the hold time).
+    ///
+    /// ```
+    /// use dspkit::effects::{Gate, GateParameters};
+    ///
+    /// let mut gate = Gate::new(GateParameters {
+    ///     open_threshold: -20.0,
+    ///     close_threshold: -30.0,
+    ///     hold: 0.0,
+    ///     ..GateParameters::default()
+    /// });
+    ///
+    /// gate.tick(&[0.5, 0.5]);
+    /// assert!(gate.is_open());
+    ///
+    /// // Between the thresholds, the gate stays open.
+    /// for _ in 0..4_800 {
+    ///     gate.tick(&[0.05, 0.05]);
+    /// }
+    /// assert!(gate.is_open());
+    ///
+    /// // Below the close threshold, the gate closes.
+    /// for _ in 0..4_800 {
+    ///     gate.tick(&[0.0, 0.0]);
+    /// }
+    /// assert!(!gate.is_open());
+    /// ```
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn set_open_threshold(&mut self, val: f32) {
+        self.parameters.open_threshold = val;
+    }
+
+    pub fn set_close_threshold(&mut self, val: f32) {
+        self.parameters.close_threshold = val;
+    }
+
+    pub fn set_attack(&mut self, val: f32) {
+        self.parameters.attack = val;
+        self.gain.set_attack(val, self.sample_rate);
+    }
+
+    pub fn set_hold(&mut self, val: f32) {
+        self.parameters.hold = val;
+        self.hold_samples = (val.max(0.0) * 0.001 * self.sample_rate as f32) as usize;
+    }
+
+    pub fn set_release(&mut self, val: f32) {
+        self.parameters.release = val;
+        self.gain.set_release(val, self.sample_rate);
+    }
+
+    pub fn set_range(&mut self, val: f32) {
+        self.parameters.range = val;
+    }
+
+    pub fn set_mode(&mut self, val: GateMode) {
+        self.parameters.mode = val;
+    }
+
+    pub fn set_ratio(&mut self, val: f32) {
+        self.parameters.ratio = val.max(1.0);
+    }
+
+    pub fn set_sidechain_hpf(&mut self, val: f32) {
+        self.parameters.sidechain_hpf = val;
+        for hpf in [&mut self.hpf_l, &mut self.hpf_r] {
+            if val > 0.0 {
+                hpf.set_highpass(val, SIDECHAIN_HPF_Q, self.sample_rate);
+            } else {
+                hpf.set_bypass();
+            }
+        }
+    }
+
+    /// Reset the gate by closing it and clearing the detector.
+    pub fn reset(&mut self) -> &mut Self {
+        self.hold_counter = 0;
+        self.open = false;
+        self.hpf_l.reset();
+        self.hpf_r.reset();
+        self.detector.reset();
+        self.gain.reset();
+        self.meter = 0.0;
+        self
+    }
+}
+
+impl GateParameters {
+    pub const fn const_default() -> Self {
+        GateParameters {
+            open_threshold: -40.0,
+            close_threshold: -46.0,
+            attack: 1.0,
+            hold: 50.0,
+            release: 100.0,
+            range: -80.0,
+            mode: GateMode::Gate,
+            ratio: 2.0,
+            sidechain_hpf: 0.0,
+        }
+    }
+}
+
+impl Default for GateParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl Default for Gate {
+    fn default() -> Self {
+        Self::new(GateParameters::const_default())
+    }
+}
+
+impl AudioNode<Stereo<f32>, Stereo<f32>> for Gate {
+    fn prepare(&mut self, sample_rate: usize) {
+        Gate::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Gate::tick(self, input)
+    }
+}
+
+impl SidechainNode<Stereo<f32>, Stereo<f32>, Stereo<f32>> for Gate {
+    fn prepare(&mut self, sample_rate: usize) {
+        Gate::prepare(self, sample_rate);
+    }
+
+    fn tick_sidechain(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        Gate::tick_sidechain(self, input, key)
+    }
+}
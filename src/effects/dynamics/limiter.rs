@@ -0,0 +1,303 @@
+use crate::components::{DelayLine, EnvelopeFollower, TruePeakDetector};
+use crate::{AudioNode, PCM, Stereo, db_to_gain, gain_to_db};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Brickwall lookahead limiter.
+///
+/// Each of the internal delay lines are limited to a maximum of `N` samples, which bounds the
+/// lookahead time.
+///
+/// The gain required to keep every (true) peak below the ceiling is held for the lookahead time
+/// with a sliding minimum, and then smoothed with a moving average over the same window. Since the
+/// audio is delayed by the lookahead, the gain has fully ramped down by the time a peak reaches
+/// the output, so peaks are brought down to the ceiling without the distortion of a hard clip.
+/// Both channels share the same gain to preserve the stereo image.
+///
+/// ```
+/// use dspkit::effects::Limiter;
+///
+/// let mut limiter = Limiter::<f32, 1024>::default();
+/// limiter.set_ceiling(-1.0);
+///
+/// // The peaks of a loud sine and of short bursts are brought down to the ceiling, and not below
+/// // it. The output is not clipped, so the ceiling is only exceeded by rounding errors.
+/// let ceiling = libm::powf(10.0, -1.0 / 20.0);
+/// let mut peak: f32 = 0.0;
+/// for n in 0..96_000 {
+///     let x = if n < 48_000 {
+///         2.0 * libm::sinf(0.05 * n as f32)
+///     } else if n % 1_000 < 3 {
+///         8.0
+///     } else {
+///         0.0
+///     };
+///     let out = limiter.tick(&[x, -x]);
+///     assert!(out[0].abs() < 1.001 * ceiling && out[1].abs() < 1.001 * ceiling);
+///     peak = peak.max(out[0].abs());
+/// }
+/// assert!(peak > 0.99 * ceiling);
+/// ```
+pub struct Limiter<S: PCM, const N: usize> {
+    parameters: LimiterParameters,
+    sample_rate: usize,
+    ceiling: f32,
+    lookahead: usize,
+    audio_l: DelayLine<S, N>,
+    audio_r: DelayLine<S, N>,
+    peak_l: TruePeakDetector,
+    peak_r: TruePeakDetector,
+    hold: SlidingMinimum<N>,
+    envelope: EnvelopeFollower,
+    average: DelayLine<f32, N>,
+    average_sum: f32,
+    average_counter: usize,
+    meter: f32,
+}
+
+/// Parameters to the limiter effect.
+#[derive(Clone, Copy)]
+pub struct LimiterParameters {
+    /// Lookahead time in milliseconds. This is also the latency of the limiter.
+    pub lookahead: f32,
+    /// Maximum output level in dB.
+    pub ceiling: f32,
+    /// Time in milliseconds for the gain reduction to recover.
+    pub release: f32,
+    /// Detect inter-sample peaks with 4x oversampling rather than only the sample values.
+    pub true_peak: bool,
+}
+
+impl<S: PCM, const N: usize> Limiter<S, N> {
+    /// Construct a limiter effect with the given initial parameters.
+    pub fn new(parameters: LimiterParameters) -> Self {
+        let mut limiter = Self::const_default();
+        limiter.parameters = parameters;
+        limiter.prepare(INITIAL_SAMPLE_RATE);
+        limiter
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The lookahead, ceiling and
+    /// release are computed in [`Limiter::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: LimiterParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            ceiling: 1.0,
+            lookahead: 0,
+            audio_l: DelayLine::const_default(),
+            audio_r: DelayLine::const_default(),
+            peak_l: TruePeakDetector::const_default(),
+            peak_r: TruePeakDetector::const_default(),
+            hold: SlidingMinimum::const_default(),
+            envelope: EnvelopeFollower::const_default(),
+            average: DelayLine::const_default(),
+            average_sum: 0.0,
+            average_counter: 0,
+            meter: 0.0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.set_lookahead(self.parameters.lookahead);
+        self.set_ceiling(self.parameters.ceiling);
+        self.set_release(self.parameters.release);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.peak_l.push(input[0]);
+        self.peak_r.push(input[1]);
+        let peak = if self.parameters.true_peak {
+            self.peak_l.peak().max(self.peak_r.peak())
+        } else {
+            self.peak_l.delayed().abs().max(self.peak_r.delayed().abs())
+        };
+
+        // Gain required for the current peak, held for the lookahead window. The detected peak
+        // already lags the input by the latency of the true-peak detector.
+        let required = if peak > self.ceiling {
+            self.ceiling / peak
+        } else {
+            1.0
+        };
+        let window = self.lookahead + 1;
+        let held = self.hold.tick(required, window);
+
+        // The reduction attacks instantly and releases smoothly, never falling below the
+        // reduction required by the held gain.
+        let reduction = self.envelope.tick(1.0 - held);
+
+        // Moving average of the gain reduction over the lookahead window. The sum is recomputed
+        // once per window so rounding errors cannot accumulate and let peaks over the ceiling.
+        self.average_sum += reduction - self.average.peek();
+        self.average.write(reduction);
+        self.average.advance();
+        self.average_counter += 1;
+        if self.average_counter >= window {
+            self.average_counter = 0;
+            self.average_sum = (1..=window).map(|delay| self.average.tap(delay)).sum();
+        }
+        let gain = (1.0 - self.average_sum / window as f32).min(1.0);
+        self.meter = -gain_to_db(gain);
+
+        let delayed_l: f32 = self.audio_l.peek().into();
+        let delayed_r: f32 = self.audio_r.peek().into();
+        self.audio_l.write_f32(input[0]);
+        self.audio_r.write_f32(input[1]);
+        self.audio_l.advance();
+        self.audio_r.advance();
+
+        [delayed_l * gain, delayed_r * gain]
+    }
+
+    /// Current gain reduction in dB, as a positive value. Useful for metering.
+    pub fn gain_reduction(&self) -> f32 {
+        self.meter
+    }
+
+    /// Delay in samples between the input and output signals.
+    pub fn latency(&self) -> usize {
+        self.lookahead + TruePeakDetector::LATENCY
+    }
+
+    /// Set the lookahead time in milliseconds. The lookahead is clamped to the capacity of the
+    /// internal delay lines, and changing it restarts the gain reduction.
+    pub fn set_lookahead(&mut self, val: f32) {
+        self.parameters.lookahead = val;
+
+        let max_lookahead = N.saturating_sub(TruePeakDetector::LATENCY);
+        let lookahead = (val * 0.001 * self.sample_rate as f32) as usize;
+        self.lookahead = lookahead.min(max_lookahead);
+
+        self.audio_l.resize(self.latency());
+        self.audio_r.resize(self.latency());
+        self.average.resize(self.lookahead + 1);
+        self.average.reset();
+        self.average_sum = 0.0;
+        self.average_counter = 0;
+        self.hold.reset();
+        self.envelope.reset();
+    }
+
+    pub fn set_ceiling(&mut self, val: f32) {
+        self.parameters.ceiling = val;
+        self.ceiling = db_to_gain(val);
+    }
+
+    pub fn set_release(&mut self, val: f32) {
+        self.parameters.release = val;
+        self.envelope.set_release(val, self.sample_rate);
+    }
+
+    pub fn set_true_peak(&mut self, val: bool) {
+        self.parameters.true_peak = val;
+    }
+
+    /// Reset the limiter by clearing the delay lines and gain reduction.
+    pub fn reset(&mut self) -> &mut Self {
+        self.audio_l.reset();
+        self.audio_r.reset();
+        self.peak_l.reset();
+        self.peak_r.reset();
+        self.hold.reset();
+        self.envelope.reset();
+        self.average.reset();
+        self.average_sum = 0.0;
+        self.average_counter = 0;
+        self.meter = 0.0;
+        self
+    }
+}
+
+impl LimiterParameters {
+    pub const fn const_default() -> Self {
+        LimiterParameters {
+            lookahead: 5.0,
+            ceiling: -1.0,
+            release: 50.0,
+            true_peak: true,
+        }
+    }
+}
+
+impl Default for LimiterParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const N: usize> Default for Limiter<S, N> {
+    fn default() -> Self {
+        Self::new(LimiterParameters::const_default())
+    }
+}
+
+impl<S: PCM, const N: usize> AudioNode<Stereo<f32>, Stereo<f32>> for Limiter<S, N> {
+    fn prepare(&mut self, sample_rate: usize) {
+        Limiter::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Limiter::tick(self, input)
+    }
+
+    fn latency(&self) -> usize {
+        Limiter::latency(self)
+    }
+}
+
+/// Minimum over a sliding window of up to `N` values, using a monotonic queue so each value is
+/// only pushed and popped once.
+struct SlidingMinimum<const N: usize> {
+    values: [f32; N],
+    stamps: [usize; N],
+    head: usize,
+    len: usize,
+    time: usize,
+}
+
+impl<const N: usize> SlidingMinimum<N> {
+    const fn const_default() -> Self {
+        Self {
+            values: [0.0; N],
+            stamps: [0; N],
+            head: 0,
+            len: 0,
+            time: 0,
+        }
+    }
+
+    /// Push a value and return the minimum over the last `window` values.
+    #[inline(always)]
+    fn tick(&mut self, input: f32, window: usize) -> f32 {
+        // Values larger than the input can never be the minimum again.
+        while self.len > 0 && self.values[self.slot(self.len - 1)] >= input {
+            self.len -= 1;
+        }
+
+        let slot = self.slot(self.len);
+        self.values[slot] = input;
+        self.stamps[slot] = self.time;
+        self.len += 1;
+
+        // Drop values which have left the window.
+        while self.time.wrapping_sub(self.stamps[self.head]) >= window {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+
+        self.time = self.time.wrapping_add(1);
+        self.values[self.head]
+    }
+
+    #[inline(always)]
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % N
+    }
+
+    fn reset(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
@@ -0,0 +1,235 @@
+use crate::components::{EnvelopeFollower, RmsDetector};
+use crate::{AudioNode, Stereo, db_to_gain, gain_to_db};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Averaging time in milliseconds of the RMS level fed to the followers.
+const RMS_WINDOW_MS: f32 = 10.0;
+/// Capacity of the RMS detector in samples, i.e. the window at sample rates up to 192 kHz.
+const RMS_CAPACITY: usize = 1_920;
+
+/// Attack times in milliseconds of the followers detecting the start of a transient.
+const ONSET_FAST_MS: f32 = 1.0;
+const ONSET_SLOW_MS: f32 = 20.0;
+/// Release time in milliseconds shared by the onset followers.
+const ONSET_RELEASE_MS: f32 = 100.0;
+
+/// Release times in milliseconds of the followers detecting the sustain of a transient.
+const SUSTAIN_FAST_MS: f32 = 30.0;
+const SUSTAIN_SLOW_MS: f32 = 300.0;
+/// Attack time in milliseconds shared by the sustain followers.
+const SUSTAIN_ATTACK_MS: f32 = 1.0;
+
+/// Maximum boost or cut in dB.
+const MAX_GAIN_DB: f32 = 24.0;
+
+/// Transient shaper with independent attack and sustain controls.
+///
+/// Transients are detected from the difference between envelope followers rather than a
+/// threshold, so the effect responds the same way to quiet and loud material. The followers track
+/// a short RMS level rather than the raw samples, so that the ripple of a steady tone does not
+/// register as a transient and steady material passes through unchanged. A fast-attack
+/// follower rises ahead of a slow-attack follower at the start of a note, and a slow-release
+/// follower stays above a fast-release follower during its decay. The differences in dB are scaled
+/// by the attack and sustain amounts to boost or cut each portion. Both channels share the same
+/// gain to preserve the stereo image.
+///
+/// ```
+/// use dspkit::effects::TransientShaper;
+///
+/// static SHAPER: TransientShaper = TransientShaper::const_default();
+/// ```
+pub struct TransientShaper {
+    parameters: TransientShaperParameters,
+    rms: RmsDetector<RMS_CAPACITY>,
+    onset_fast: EnvelopeFollower,
+    onset_slow: EnvelopeFollower,
+    sustain_fast: EnvelopeFollower,
+    sustain_slow: EnvelopeFollower,
+    output: f32,
+}
+
+/// Parameters to the transient shaper effect.
+#[derive(Clone, Copy)]
+pub struct TransientShaperParameters {
+    /// Gain applied to the attack portion of transients. -1.0 = soften to 1.0 = emphasize.
+    pub attack: f32,
+    /// Gain applied to the sustain portion of transients. -1.0 = shorten to 1.0 = lengthen.
+    pub sustain: f32,
+    /// Gain in dB applied to the output.
+    pub output: f32,
+}
+
+impl TransientShaper {
+    /// Construct a transient shaper effect with the given initial parameters.
+    pub fn new(parameters: TransientShaperParameters) -> Self {
+        let mut shaper = Self::const_default();
+        shaper.parameters = parameters;
+        shaper.prepare(INITIAL_SAMPLE_RATE);
+        shaper
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The time constants are
+    /// computed in [`TransientShaper::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: TransientShaperParameters::const_default(),
+            rms: RmsDetector::const_default(),
+            onset_fast: EnvelopeFollower::const_default(),
+            onset_slow: EnvelopeFollower::const_default(),
+            sustain_fast: EnvelopeFollower::const_default(),
+            sustain_slow: EnvelopeFollower::const_default(),
+            output: 1.0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.rms.set_window(RMS_WINDOW_MS, sample_rate);
+
+        self.onset_fast.set_attack(ONSET_FAST_MS, sample_rate);
+        self.onset_slow.set_attack(ONSET_SLOW_MS, sample_rate);
+        self.onset_fast.set_release(ONSET_RELEASE_MS, sample_rate);
+        self.onset_slow.set_release(ONSET_RELEASE_MS, sample_rate);
+
+        self.sustain_fast.set_attack(SUSTAIN_ATTACK_MS, sample_rate);
+        self.sustain_slow.set_attack(SUSTAIN_ATTACK_MS, sample_rate);
+        self.sustain_fast.set_release(SUSTAIN_FAST_MS, sample_rate);
+        self.sustain_slow.set_release(SUSTAIN_SLOW_MS, sample_rate);
+
+        self.set_output(self.parameters.output);
+    }
+
+    /// Process a frame. Steady tones pass through unchanged, whatever the attack and sustain.
+    ///
+    /// ```
+    /// use dspkit::effects::TransientShaper;
+    ///
+    /// for frequency in [50.0, 100.0, 1_000.0] {
+    ///     for (attack, sustain) in [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)] {
+    ///         let mut shaper = TransientShaper::default();
+    ///         shaper.set_attack(attack);
+    ///         shaper.set_sustain(sustain);
+    ///
+    ///         // Compare the power of the input and output once the followers have settled.
+    ///         let (mut input, mut output) = (0.0, 0.0);
+    ///         for n in 0..96_000 {
+    ///             let phase = 2.0 * core::f32::consts::PI * frequency * n as f32 / 48_000.0;
+    ///             let x = 0.5 * libm::sinf(phase);
+    ///             let out = shaper.tick(&[x, x]);
+    ///             if n >= 48_000 {
+    ///                 input += x * x;
+    ///                 output += out[0] * out[0];
+    ///             }
+    ///         }
+    ///         let gain = 10.0 * libm::log10f(output / input);
+    ///         assert!(gain.abs() < 0.01, "{frequency} Hz: {gain} dB");
+    ///     }
+    /// }
+    /// ```
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let level = self.rms.tick(&input[0].abs().max(input[1].abs()));
+
+        let onset =
+            gain_to_db(self.onset_fast.tick(level)) - gain_to_db(self.onset_slow.tick(level));
+        let sustain =
+            gain_to_db(self.sustain_slow.tick(level)) - gain_to_db(self.sustain_fast.tick(level));
+
+        let gain =
+            self.parameters.attack * onset.max(0.0) + self.parameters.sustain * sustain.max(0.0);
+        let gain = db_to_gain(gain.clamp(-MAX_GAIN_DB, MAX_GAIN_DB)) * self.output;
+
+        [input[0] * gain, input[1] * gain]
+    }
+
+    /// Set the gain applied to the attack portion of transients, clamped to -1.0 to 1.0.
+    ///
+    /// ```
+    /// use dspkit::effects::TransientShaper;
+    ///
+    /// let mut shaper = TransientShaper::default();
+    /// shaper.set_attack(1.0);
+    ///
+    /// // The onset of a step is boosted, while its steady level is left untouched.
+    /// let out: Vec<f32> = (0..9_600).map(|_| shaper.tick(&[0.5, 0.5])[0]).collect();
+    /// assert!(out[48] > 2.0 * out[9_599]);
+    /// assert!((out[9_599] - 0.5).abs() < 1e-3);
+    /// ```
+    pub fn set_attack(&mut self, val: f32) {
+        self.parameters.attack = val.clamp(-1.0, 1.0);
+    }
+
+    /// Set the gain applied to the sustain portion of transients, clamped to -1.0 to 1.0.
+    ///
+    /// ```
+    /// use dspkit::effects::TransientShaper;
+    ///
+    /// let mut shaper = TransientShaper::default();
+    /// shaper.set_sustain(-1.0);
+    ///
+    /// // The decaying tail of a note is cut.
+    /// let (mut input, mut output) = (0.0, 0.0);
+    /// for n in 0..24_000 {
+    ///     let x = if n < 2_400 {
+    ///         0.5
+    ///     } else {
+    ///         0.5 * libm::expf(-((n - 2_400) as f32) / 4_800.0)
+    ///     };
+    ///     let out = shaper.tick(&[x, x]);
+    ///     if n >= 4_800 {
+    ///         input += x * x;
+    ///         output += out[0] * out[0];
+    ///     }
+    /// }
+    /// assert!(output < 0.5 * input);
+    /// ```
+    pub fn set_sustain(&mut self, val: f32) {
+        self.parameters.sustain = val.clamp(-1.0, 1.0);
+    }
+
+    pub fn set_output(&mut self, val: f32) {
+        self.parameters.output = val;
+        self.output = db_to_gain(val);
+    }
+
+    /// Reset the transient shaper by clearing the level detector and envelope followers.
+    pub fn reset(&mut self) -> &mut Self {
+        self.rms.reset();
+        self.onset_fast.reset();
+        self.onset_slow.reset();
+        self.sustain_fast.reset();
+        self.sustain_slow.reset();
+        self
+    }
+}
+
+impl TransientShaperParameters {
+    pub const fn const_default() -> Self {
+        TransientShaperParameters {
+            attack: 0.0,
+            sustain: 0.0,
+            output: 0.0,
+        }
+    }
+}
+
+impl Default for TransientShaperParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl Default for TransientShaper {
+    fn default() -> Self {
+        Self::new(TransientShaperParameters::const_default())
+    }
+}
+
+impl AudioNode<Stereo<f32>, Stereo<f32>> for TransientShaper {
+    fn prepare(&mut self, sample_rate: usize) {
+        TransientShaper::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        TransientShaper::tick(self, input)
+    }
+}
@@ -0,0 +1,382 @@
+use crate::components::{DelayLine, Lfo, LfoShape};
+use crate::{AudioNode, PCM, Stereo};
+
+use libm::{ceilf, powf, sqrtf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Length of the shortest delay line relative to the longest.
+const SHORTEST_LINE: f32 = 0.35;
+
+/// Spread of the modulation rates between the lines, relative to the rate.
+const RATE_SPREAD: f32 = 0.25;
+
+/// Shortest reverberation time in seconds, which keeps the absorption gains defined.
+const MIN_RT60: f32 = 0.01;
+
+/// Feedback delay network reverb with `LINES` delay lines of up to `N` samples.
+///
+/// The outputs of the delay lines are filtered by absorption filters, mixed by an orthogonal
+/// feedback matrix, and fed back into the lines along with the input. Since an orthogonal matrix
+/// preserves energy, the decay is entirely set by the absorption filters, which are derived for
+/// each line from its length so that every line decays at the same rate. Each absorption filter is
+/// a one-pole low-pass, matching the reverberation time at DC and at the Nyquist frequency.
+///
+/// The line lengths are spread between the size and about a third of it, and rounded to prime
+/// numbers of samples so the echoes of different lines rarely coincide. Each line is modulated by
+/// its own LFO to smear the resonances of the network. Even lines feed the left output and odd
+/// lines feed the right output, so at least two lines are required.
+///
+/// ```
+/// use dspkit::effects::{Fdn, FdnParameters};
+///
+/// static FDN: Fdn<f32, 8, 4096> = Fdn::const_default();
+///
+/// // Estimate the reverberation time from the slope of the energy of the impulse response, in
+/// // windows of 50 ms between 0.2 s and half of the configured time later.
+/// fn rt60(rt60: f32, mod_depth: f32) -> f32 {
+///     let mut fdn = Box::new(Fdn::<f32, 8, 4096>::new(FdnParameters {
+///         rt60_low: rt60,
+///         rt60_high: rt60,
+///         mod_depth,
+///         mix: 1.0,
+///         ..FdnParameters::default()
+///     }));
+///     let mut energy = [0.0; 60];
+///     for n in 0..144_000 {
+///         let x = if n == 0 { 1.0 } else { 0.0 };
+///         let out = fdn.tick(&[x, x]);
+///         energy[n / 2_400] += out[0] * out[0] + out[1] * out[1];
+///     }
+///     let (start, end) = (4, 4 + (10.0 * rt60) as usize);
+///     let drop = 10.0 * libm::log10f(energy[start] / energy[end]);
+///     60.0 * 0.05 * (end - start) as f32 / drop
+/// }
+///
+/// // The tail decays by 60 dB over the configured time, slightly faster with modulation.
+/// for time in [0.5, 1.0, 2.0] {
+///     assert!((rt60(time, 0.0) / time - 1.0).abs() < 0.05);
+///     assert!((rt60(time, 0.3) / time - 1.0).abs() < 0.15);
+/// }
+/// ```
+pub struct Fdn<S: PCM, const LINES: usize, const N: usize> {
+    parameters: FdnParameters,
+    sample_rate: usize,
+    excursion: f32,
+    lines: [FdnLine<S, N>; LINES],
+}
+
+/// Feedback matrix mixing the outputs of the delay lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FdnMatrix {
+    /// Normalized Hadamard matrix, mixing every line into every other line with equal weight.
+    /// Computed with a fast Walsh-Hadamard transform, and only available when the number of lines
+    /// is a power of two. Otherwise, falls back to [`FdnMatrix::Householder`].
+    Hadamard,
+    /// Householder reflection, which mixes less densely than the Hadamard matrix but is available
+    /// for any number of lines.
+    Householder,
+}
+
+/// Parameters to the FDN reverb effect.
+#[derive(Clone, Copy)]
+pub struct FdnParameters {
+    /// Feedback matrix mixing the outputs of the delay lines.
+    pub matrix: FdnMatrix,
+    /// Length in milliseconds of the longest delay line. The lengths are clamped to the capacity
+    /// of the delay lines.
+    pub size: f32,
+    /// Time in seconds for low frequencies to decay by 60 dB.
+    pub rt60_low: f32,
+    /// Time in seconds for high frequencies to decay by 60 dB.
+    pub rt60_high: f32,
+    /// Rate of the modulation of the delay lengths in Hz.
+    pub mod_rate: f32,
+    /// Peak modulation of the delay lengths in milliseconds. The interpolation of the modulated
+    /// delays absorbs some high frequencies, which shortens the reverberation time slightly.
+    pub mod_depth: f32,
+    /// Mix of the reverb signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+/// A delay line with its absorption filter and modulation.
+#[derive(Clone, Copy)]
+struct FdnLine<S: PCM, const N: usize> {
+    line: DelayLine<S, N>,
+    length: f32,
+    gain: f32,
+    pole: f32,
+    absorbed: f32,
+    lfo: Lfo,
+}
+
+impl<S: PCM, const LINES: usize, const N: usize> Fdn<S, LINES, N> {
+    /// Construct an FDN reverb effect with the given initial parameters.
+    ///
+    /// ```
+    /// use dspkit::effects::{Fdn, FdnMatrix, FdnParameters};
+    ///
+    /// let fdn = Fdn::<f32, 16, 8192>::new(FdnParameters {
+    ///     matrix: FdnMatrix::Hadamard,
+    ///     size: 120.0,
+    ///     rt60_low: 3.0,
+    ///     rt60_high: 1.2,
+    ///     mod_rate: 0.3,
+    ///     mod_depth: 0.5,
+    ///     mix: 0.25,
+    /// });
+    /// ```
+    pub fn new(parameters: FdnParameters) -> Self {
+        let mut fdn = Self::const_default();
+        fdn.parameters = parameters;
+        fdn.prepare(INITIAL_SAMPLE_RATE);
+        fdn
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The delay lengths and
+    /// absorption filters are computed in [`Fdn::prepare`], which must be called before
+    /// processing.
+    pub const fn const_default() -> Self {
+        const {
+            assert!(LINES >= 2, "an FDN requires at least two delay lines");
+        }
+
+        Self {
+            parameters: FdnParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            excursion: 0.0,
+            lines: [FdnLine::const_default(); LINES],
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        for (idx, line) in self.lines.iter_mut().enumerate() {
+            line.lfo.prepare(sample_rate);
+            line.lfo.set_phase(idx as f32 / LINES as f32);
+        }
+        self.set_mod_rate(self.parameters.mod_rate);
+        self.set_mod_depth(self.parameters.mod_depth);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let mut outputs = [0.0; LINES];
+        for (output, line) in outputs.iter_mut().zip(self.lines.iter_mut()) {
+            *output = line.read(self.excursion);
+        }
+
+        let (mut wet_l, mut wet_r) = (0.0, 0.0);
+        for (idx, output) in outputs.iter().enumerate() {
+            if idx % 2 == 0 {
+                wet_l += output;
+            } else {
+                wet_r += output;
+            }
+        }
+        let scale = 1.0 / sqrtf((LINES / 2) as f32);
+        let wet_l = wet_l * scale;
+        let wet_r = wet_r * scale;
+
+        self.mix(&mut outputs);
+        for (idx, (line, feedback)) in self.lines.iter_mut().zip(outputs).enumerate() {
+            line.write(input[idx % 2] + feedback);
+        }
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (wet_l - input[0]),
+            input[1] + mix * (wet_r - input[1]),
+        ]
+    }
+
+    /// Apply the feedback matrix in place.
+    #[inline(always)]
+    fn mix(&self, values: &mut [f32; LINES]) {
+        match self.parameters.matrix {
+            FdnMatrix::Hadamard if LINES.is_power_of_two() => {
+                let mut width = 1;
+                while width < LINES {
+                    for start in (0..LINES).step_by(2 * width) {
+                        for idx in start..start + width {
+                            let (a, b) = (values[idx], values[idx + width]);
+                            values[idx] = a + b;
+                            values[idx + width] = a - b;
+                        }
+                    }
+                    width *= 2;
+                }
+                let scale = 1.0 / sqrtf(LINES as f32);
+                values.iter_mut().for_each(|val| *val *= scale);
+            }
+            _ => {
+                let reflection = 2.0 / LINES as f32 * values.iter().sum::<f32>();
+                values.iter_mut().for_each(|val| *val -= reflection);
+            }
+        }
+    }
+
+    pub fn set_matrix(&mut self, val: FdnMatrix) {
+        self.parameters.matrix = val;
+    }
+
+    /// Set the length of the longest delay line in milliseconds. The lengths are clamped to the
+    /// capacity of the delay lines, leaving room for the modulation.
+    pub fn set_size(&mut self, val: f32) {
+        self.parameters.size = val.max(0.0);
+
+        let longest = self.parameters.size * 0.001 * self.sample_rate as f32;
+        let max_length = N.saturating_sub(ceilf(self.excursion) as usize + 2).max(1);
+        for (idx, line) in self.lines.iter_mut().enumerate() {
+            let position = idx as f32 / (LINES - 1) as f32;
+            let length = longest * powf(SHORTEST_LINE, 1.0 - position);
+            let length = nearest_prime(length as usize).min(max_length);
+            line.length = length as f32;
+            line.line
+                .resize(length + ceilf(self.excursion) as usize + 2);
+        }
+
+        self.update_absorption();
+    }
+
+    pub fn set_rt60_low(&mut self, val: f32) {
+        self.parameters.rt60_low = val;
+        self.update_absorption();
+    }
+
+    pub fn set_rt60_high(&mut self, val: f32) {
+        self.parameters.rt60_high = val;
+        self.update_absorption();
+    }
+
+    pub fn set_mod_rate(&mut self, val: f32) {
+        self.parameters.mod_rate = val;
+        for (idx, line) in self.lines.iter_mut().enumerate() {
+            let spread = 1.0 + RATE_SPREAD * (idx as f32 / LINES as f32 - 0.5);
+            line.lfo.set_frequency(val * spread);
+        }
+    }
+
+    /// Set the peak modulation in milliseconds, which also updates the delay lengths to leave
+    /// room for the modulation.
+    pub fn set_mod_depth(&mut self, val: f32) {
+        self.parameters.mod_depth = val.max(0.0);
+        self.excursion = self.parameters.mod_depth * 0.001 * self.sample_rate as f32;
+        self.set_size(self.parameters.size);
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Derive the absorption filter of each line from its length and the reverberation times.
+    fn update_absorption(&mut self) {
+        let rt60_low = self.parameters.rt60_low.max(MIN_RT60);
+        let rt60_high = self.parameters.rt60_high.max(MIN_RT60);
+        let sample_rate = self.sample_rate as f32;
+
+        for line in self.lines.iter_mut() {
+            // Gain per trip around the line for a decay of 60 dB over the reverberation time.
+            let gain_low = powf(10.0, -3.0 * line.length / (rt60_low * sample_rate));
+            let gain_high = powf(10.0, -3.0 * line.length / (rt60_high * sample_rate));
+
+            // The one-pole low-pass g * (1 - p) / (1 - p * z^-1) has a gain of g at DC, and
+            // g * (1 - p) / (1 + p) at the Nyquist frequency.
+            let ratio = (gain_high / gain_low).min(1.0);
+            line.pole = (1.0 - ratio) / (1.0 + ratio);
+            line.gain = gain_low * (1.0 - line.pole);
+        }
+    }
+
+    /// Reset the FDN by clearing all of the delay lines and absorption filters.
+    pub fn reset(&mut self) -> &mut Self {
+        for line in self.lines.iter_mut() {
+            line.line.reset();
+            line.absorbed = 0.0;
+        }
+        self
+    }
+}
+
+impl<S: PCM, const N: usize> FdnLine<S, N> {
+    const fn const_default() -> Self {
+        Self {
+            line: DelayLine::const_default(),
+            length: 1.0,
+            gain: 0.0,
+            pole: 0.0,
+            absorbed: 0.0,
+            lfo: Lfo::new(LfoShape::Sine, 1.0, INITIAL_SAMPLE_RATE),
+        }
+    }
+
+    /// Read the modulated output of the line, filtered by the absorption filter.
+    #[inline(always)]
+    fn read(&mut self, excursion: f32) -> f32 {
+        let delay = self.length + excursion * self.lfo.tick();
+        let delayed = self.line.tap_fractional(delay);
+        self.absorbed = self.gain * delayed + self.pole * self.absorbed;
+        self.absorbed
+    }
+
+    #[inline(always)]
+    fn write(&mut self, input: f32) {
+        self.line.write_f32(input);
+        self.line.advance();
+    }
+}
+
+impl FdnParameters {
+    pub const fn const_default() -> Self {
+        FdnParameters {
+            matrix: FdnMatrix::Householder,
+            size: 80.0,
+            rt60_low: 2.0,
+            rt60_high: 1.0,
+            mod_rate: 0.5,
+            mod_depth: 0.3,
+            mix: 0.3,
+        }
+    }
+}
+
+impl Default for FdnParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const LINES: usize, const N: usize> Default for Fdn<S, LINES, N> {
+    fn default() -> Self {
+        Self::new(FdnParameters::const_default())
+    }
+}
+
+impl<S: PCM, const LINES: usize, const N: usize> AudioNode<Stereo<f32>, Stereo<f32>>
+    for Fdn<S, LINES, N>
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        Fdn::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Fdn::tick(self, input)
+    }
+}
+
+/// The prime number closest to `n`, preferring the smaller prime on ties.
+fn nearest_prime(n: usize) -> usize {
+    (0..n)
+        .find_map(|offset| {
+            [n.saturating_sub(offset), n + offset]
+                .into_iter()
+                .find(|&candidate| is_prime(candidate))
+        })
+        .unwrap_or(2)
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
@@ -2,10 +2,13 @@ mod ap;
 mod lbcf;
 mod tuning;
 
+use crate::components::{Biquad, DelayLine, Lfo, LfoShape};
 use crate::{PCM, Stereo};
 use ap::AllPass;
 use lbcf::Comb;
 
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
 /// Implementation of the "freeverb" algorithm.
 ///
 /// Each of the internal combs and allpass filters are limited to a maximum of `N` samples.
@@ -14,6 +17,12 @@ use lbcf::Comb;
 /// filtered-feedback comb-filters followed by four allpass filters in series for the left and
 /// right channels. The right channels are slightly deturned to produce a stereo effect.
 ///
+/// On top of the original algorithm, the input can be delayed and filtered, the delay times can be
+/// scaled, and the comb lengths can be slowly modulated to reduce ringing. All delay times are
+/// given in seconds, so the tuning is independent of the sample rate. The input is summed to mono
+/// by default, or each side of the combs can be fed by its own channel with
+/// [`FreeverbInput::Stereo`].
+///
 /// A complete analysis of the algorithm and Comb/All Pass blocks can be found [here](https://www.dsprelated.com/freebooks/pasp/Freeverb.html).
 pub struct Freeverb<S: PCM, const N: usize> {
     parameters: FreeverbParameters,
@@ -22,6 +31,18 @@ pub struct Freeverb<S: PCM, const N: usize> {
     combs_r: [Comb<S, N>; tuning::NUM_COMBS],
     allpass_l: [AllPass<S, N>; tuning::NUM_ALLPASS],
     allpass_r: [AllPass<S, N>; tuning::NUM_ALLPASS],
+    sample_rate: usize,
+    excursion: f32,
+    predelay: [DelayLine<S, N>; 2],
+    input_hpf: [Biquad; 2],
+    input_lpf: [Biquad; 2],
+    output_hpf: [Biquad; 2],
+    output_lpf: [Biquad; 2],
+    lfos: [Lfo; tuning::NUM_COMBS],
+    hold: bool,
+    /// Current amount of freeze, 0.0 = active to 1.0 = frozen, ramping towards the target.
+    freeze: f32,
+    freeze_step: f32,
 }
 
 /// Mode for the reverb effect.
@@ -31,7 +52,7 @@ pub enum FreeverbMode {
     /// Normal mode for a live reverb effect.
     Active = 0,
     /// "Freezes" the reverb, allowing for an infinite tail. Will not incorporate newer signals
-    /// until unfrozen.
+    /// until unfrozen. The reverb fades into and out of the freeze over the freeze fade time.
     Frozen = 1,
 }
 
@@ -46,11 +67,28 @@ impl From<u16> for FreeverbMode {
     }
 }
 
+/// Routing of the input into the combs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreeverbInput {
+    /// Sum the input to mono, feeding the same signal to the left and right combs.
+    Mono,
+    /// Feed the left and right combs from their own channels, preserving the stereo image of the
+    /// input. The amount of the opposite channel mixed in is set by the cross-feed.
+    Stereo,
+}
+
 /// Parameters to the freeverb effect.
 #[derive(Clone, Copy)]
 pub struct FreeverbParameters {
     /// Mode for the reverb (active or frozen).
     pub mode: FreeverbMode,
+    /// Time in milliseconds to fade into and out of the freeze, either from the mode or the hold.
+    pub freeze_fade: f32,
+    /// Routing of the input into the combs (mono or stereo).
+    pub input: FreeverbInput,
+    /// Amount of the opposite channel fed into each side in [`FreeverbInput::Stereo`].
+    /// 0.0 = independent channels to 1.0 = mono sum.
+    pub cross_feed: f32,
     /// Size of the room to model reflections. 0.0 = small room to 1.0 = large room.
     pub room_size: f32,
     /// Amount of damping applied to high frequencies over time. 0.0 = no damping, 1.0 = full
@@ -62,6 +100,25 @@ pub struct FreeverbParameters {
     pub dry: f32,
     /// Spatial spread of the reverb effect. 0.0 = mono to 1.0 = full stereo.
     pub width: f32,
+    /// Delay in milliseconds before the input reaches the reverb, limited to `N` samples.
+    pub predelay: f32,
+    /// Scale of all of the delay times. 1.0 = original delay times.
+    pub size: f32,
+    /// Offset of the right channel delay times relative to the left, scaled with the size.
+    /// 0.0 = no offset to 1.0 = original offset of 23 samples at 44.1 kHz.
+    pub spread: f32,
+    /// Rate of the modulation of the comb lengths in Hz.
+    pub mod_rate: f32,
+    /// Peak modulation of the comb lengths in milliseconds. 0.0 = no modulation.
+    pub mod_depth: f32,
+    /// Cutoff in Hz of the high-pass filter applied to the input. 0.0 = disabled.
+    pub input_hpf: f32,
+    /// Cutoff in Hz of the low-pass filter applied to the input. 0.0 = disabled.
+    pub input_lpf: f32,
+    /// Cutoff in Hz of the high-pass filter applied to the reverb output. 0.0 = disabled.
+    pub output_hpf: f32,
+    /// Cutoff in Hz of the low-pass filter applied to the reverb output. 0.0 = disabled.
+    pub output_lpf: f32,
 }
 
 struct FreeverbDerivedVars {
@@ -74,8 +131,8 @@ struct FreeverbDerivedVars {
 }
 
 impl<S: PCM, const N: usize> Freeverb<S, N> {
-    /// Construct a freeverb effect with the given initial parameters. All float parameters are
-    /// checked to be within the range 0.0..=1.0.
+    /// Construct a freeverb effect with the given initial parameters. The feedback and damping
+    /// of the combs are clamped to the range 0.0..=1.0.
     //
     /// ```
     /// use dspkit::effects::{Freeverb, FreeverbParameters, FreeverbMode};
@@ -85,18 +142,18 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
     ///     damp: 0.5,
     ///     wet: 0.7,
     ///     dry: 0.3,
-    ///     width: 0.4
+    ///     width: 0.4,
+    ///     predelay: 20.0,
+    ///     ..FreeverbParameters::default()
     /// });
     /// ```
     pub fn new(parameters: FreeverbParameters) -> Self {
-        Self {
-            parameters,
-            derived: compute_derived_parameters(parameters),
-            combs_l: [Comb::const_default(); tuning::NUM_COMBS],
-            combs_r: [Comb::const_default(); tuning::NUM_COMBS],
-            allpass_l: [AllPass::const_default(); tuning::NUM_ALLPASS],
-            allpass_r: [AllPass::const_default(); tuning::NUM_ALLPASS],
-        }
+        let mut freeverb = Self::const_default();
+        freeverb.parameters = parameters;
+        freeverb.derived = compute_derived_parameters(parameters);
+        // Start in the requested mode, without fading.
+        freeverb.freeze = freeverb.freeze_target();
+        freeverb
     }
 
     /// Default const constructor, i.e. can be created at compile-time.   
@@ -114,42 +171,125 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
             combs_r: [Comb::const_default(); tuning::NUM_COMBS],
             allpass_l: [AllPass::const_default(); tuning::NUM_ALLPASS],
             allpass_r: [AllPass::const_default(); tuning::NUM_ALLPASS],
+            sample_rate: INITIAL_SAMPLE_RATE,
+            excursion: 0.0,
+            predelay: [DelayLine::const_default(); 2],
+            input_hpf: [Biquad::const_default(); 2],
+            input_lpf: [Biquad::const_default(); 2],
+            output_hpf: [Biquad::const_default(); 2],
+            output_lpf: [Biquad::const_default(); 2],
+            lfos: [Lfo::new(
+                LfoShape::Sine,
+                tuning::INITIAL_MOD_RATE,
+                INITIAL_SAMPLE_RATE,
+            ); tuning::NUM_COMBS],
+            hold: false,
+            freeze: 0.0,
+            freeze_step: 1.0,
         }
     }
 
     #[inline]
     pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
         self.derived = compute_derived_parameters(self.parameters);
+        self.update_combs();
+        self.set_freeze_fade(self.parameters.freeze_fade);
 
-        for (comb, delay_seconds) in self.combs_l.iter_mut().zip(tuning::COMB_SECOND_TUNINGS) {
-            comb.set_feedback(self.derived.room_size);
-            comb.set_mix(self.derived.damp);
-            comb.set_delay(delay_seconds, sample_rate);
+        for (idx, lfo) in self.lfos.iter_mut().enumerate() {
+            lfo.prepare(sample_rate);
+            lfo.set_phase(idx as f32 / tuning::NUM_COMBS as f32);
         }
 
-        for (comb, delay_seconds) in self.combs_r.iter_mut().zip(tuning::COMB_SECOND_TUNINGS) {
-            comb.set_feedback(self.derived.room_size);
-            comb.set_mix(self.derived.damp);
-            comb.set_delay(delay_seconds + tuning::STEREO_SPREAD_SEC, sample_rate);
-        }
+        self.set_predelay(self.parameters.predelay);
+        self.set_mod_rate(self.parameters.mod_rate);
+        // Also updates the delay times, leaving room for the modulation.
+        self.set_mod_depth(self.parameters.mod_depth);
+        self.set_input_hpf(self.parameters.input_hpf);
+        self.set_input_lpf(self.parameters.input_lpf);
+        self.set_output_hpf(self.parameters.output_hpf);
+        self.set_output_lpf(self.parameters.output_lpf);
+    }
 
-        for (allpass, delay_seconds) in self
-            .allpass_l
+    /// Set the delay times of the combs and allpass filters from the size and stereo spread.
+    fn update_delays(&mut self) {
+        let size = self.parameters.size;
+        let spread = tuning::STEREO_SPREAD_SEC * self.parameters.spread * size;
+
+        for ((comb_l, comb_r), delay_seconds) in self
+            .combs_l
             .iter_mut()
-            .zip(tuning::ALLPASS_SECOND_TUNINGS)
+            .zip(self.combs_r.iter_mut())
+            .zip(tuning::COMB_SECOND_TUNINGS)
         {
-            allpass.set_delay(delay_seconds, sample_rate);
+            let delay_seconds = delay_seconds * size;
+            comb_l.set_delay(delay_seconds, self.excursion, self.sample_rate);
+            comb_r.set_delay(delay_seconds + spread, self.excursion, self.sample_rate);
         }
 
-        for (allpass, delay_seconds) in self
-            .allpass_r
+        for ((allpass_l, allpass_r), delay_seconds) in self
+            .allpass_l
             .iter_mut()
+            .zip(self.allpass_r.iter_mut())
             .zip(tuning::ALLPASS_SECOND_TUNINGS)
         {
-            allpass.set_delay(delay_seconds + tuning::STEREO_SPREAD_SEC, sample_rate);
+            let delay_seconds = delay_seconds * size;
+            allpass_l.set_delay(delay_seconds, self.sample_rate);
+            allpass_r.set_delay(delay_seconds + spread, self.sample_rate);
         }
     }
 
+    /// Set the feedback and damping of the combs, blending towards an infinite sustain by the
+    /// amount of freeze. Both are clamped to the range `0.0..=1.0` accepted by the combs.
+    fn update_combs(&mut self) {
+        let room_size = self.derived.room_size.clamp(0.0, 1.0);
+        let feedback = room_size + self.freeze * (1.0 - room_size);
+        let damp = (self.derived.damp * (1.0 - self.freeze)).clamp(0.0, 1.0);
+        for comb in self.combs_l.iter_mut().chain(self.combs_r.iter_mut()) {
+            comb.set_feedback(feedback);
+            comb.set_mix(damp);
+        }
+    }
+
+    /// Whether the reverb should be frozen, from the mode or the hold.
+    #[inline(always)]
+    fn freeze_target(&self) -> f32 {
+        match (self.parameters.mode, self.hold) {
+            (FreeverbMode::Frozen, _) | (_, true) => 1.0,
+            (FreeverbMode::Active, false) => 0.0,
+        }
+    }
+
+    /// Move the amount of freeze one sample towards the target.
+    #[inline(always)]
+    fn update_freeze(&mut self) {
+        let target = self.freeze_target();
+        if self.freeze != target {
+            self.freeze = if self.freeze < target {
+                (self.freeze + self.freeze_step).min(target)
+            } else {
+                (self.freeze - self.freeze_step).max(target)
+            };
+            self.update_combs();
+        }
+    }
+
+    /// Scale, delay and filter the input to the combs of a channel.
+    #[inline(always)]
+    fn condition_input(&mut self, channel: usize, input: f32) -> f32 {
+        let input = self.derived.gain * (1.0 - self.freeze) * input;
+        let predelay = &mut self.predelay[channel];
+        let input = if predelay.is_empty() {
+            input
+        } else {
+            let delayed = predelay.peek().into();
+            predelay.write_f32(input);
+            predelay.advance();
+            delayed
+        };
+        self.input_lpf[channel].tick(&self.input_hpf[channel].tick(&input))
+    }
+
     pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
         let in_l = input[0];
         let in_r = input[1];
@@ -157,14 +297,31 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
         let mut out_l = f32::PCM_EQUILIBRIUM;
         let mut out_r = f32::PCM_EQUILIBRIUM;
 
-        let mono_input = self.derived.gain * 0.5 * (in_l + in_r);
-
-        for comb in self.combs_l.iter_mut() {
-            out_l += comb.tick(&mono_input);
-        }
-
-        for comb in self.combs_r.iter_mut() {
-            out_r += comb.tick(&mono_input);
+        self.update_freeze();
+
+        let (comb_in_l, comb_in_r) = match self.parameters.input {
+            FreeverbInput::Mono => {
+                let mono_input = self.condition_input(0, 0.5 * (in_l + in_r));
+                (mono_input, mono_input)
+            }
+            FreeverbInput::Stereo => {
+                let cross = 0.5 * self.parameters.cross_feed.clamp(0.0, 1.0);
+                let left = self.condition_input(0, in_l + cross * (in_r - in_l));
+                let right = self.condition_input(1, in_r + cross * (in_l - in_r));
+                (left, right)
+            }
+        };
+
+        // The right combs are modulated in the opposite direction to decorrelate the channels.
+        for ((comb_l, comb_r), lfo) in self
+            .combs_l
+            .iter_mut()
+            .zip(self.combs_r.iter_mut())
+            .zip(self.lfos.iter_mut())
+        {
+            let modulation = self.excursion * lfo.tick();
+            out_l += comb_l.tick(&comb_in_l, modulation);
+            out_r += comb_r.tick(&comb_in_r, -modulation);
         }
 
         for allpass in self.allpass_l.iter_mut() {
@@ -175,6 +332,9 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
             out_r = allpass.tick(&out_r);
         }
 
+        out_l = self.output_lpf[0].tick(&self.output_hpf[0].tick(&out_l));
+        out_r = self.output_lpf[1].tick(&self.output_hpf[1].tick(&out_r));
+
         let wet_l = out_l * self.derived.wet_l + out_r * self.derived.wet_r;
         let wet_r = out_l * self.derived.wet_r + out_r * self.derived.wet_l;
 
@@ -184,30 +344,186 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
         [out_l, out_r]
     }
 
+    /// Set the size of the room. The feedback of the combs is clamped to 1.0, so larger values
+    /// cannot make the reverb unstable.
+    ///
+    /// ```
+    /// use dspkit::effects::Freeverb;
+    ///
+    /// let mut reverb = Freeverb::<f32, 4096>::default();
+    /// reverb.prepare(48_000);
+    /// reverb.set_room_size(1.1);
+    /// reverb.set_damp(1.5);
+    /// reverb.tick(&[1.0, 1.0]);
+    /// ```
     pub fn set_room_size(&mut self, val: f32) {
         self.parameters.room_size = val;
+        self.derived = compute_derived_parameters(self.parameters);
+        self.update_combs();
     }
 
     pub fn set_damp(&mut self, val: f32) {
         self.parameters.damp = val;
+        self.derived = compute_derived_parameters(self.parameters);
+        self.update_combs();
     }
 
     pub fn set_wet(&mut self, val: f32) {
         self.parameters.wet = val;
+        self.derived = compute_derived_parameters(self.parameters);
     }
 
     pub fn set_dry(&mut self, val: f32) {
         self.parameters.dry = val;
+        self.derived = compute_derived_parameters(self.parameters);
     }
 
     pub fn set_width(&mut self, val: f32) {
         self.parameters.width = val;
+        self.derived = compute_derived_parameters(self.parameters);
     }
 
+    /// Set the mode, fading into or out of the freeze from the next sample.
     pub fn set_mode(&mut self, val: FreeverbMode) {
         self.parameters.mode = val;
     }
 
+    /// Hold the tail with an infinite sustain while `val` is true, regardless of the mode. Fades
+    /// like [`FreeverbMode::Frozen`].
+    ///
+    /// ```
+    /// use dspkit::components::WhiteNoise;
+    /// use dspkit::effects::{Freeverb, FreeverbMode};
+    ///
+    /// let mut reverbs: [Freeverb<f32, 4096>; 3] = Default::default();
+    /// for reverb in reverbs.iter_mut() {
+    ///     reverb.prepare(48_000);
+    /// }
+    /// let mut noise = WhiteNoise::default();
+    /// for _ in 0..4_800 {
+    ///     let x = noise.tick();
+    ///     reverbs.iter_mut().for_each(|reverb| _ = reverb.tick(&[x, x]));
+    /// }
+    ///
+    /// // Holding or freezing takes effect while processing, without preparing the reverb again.
+    /// reverbs[1].set_hold(true);
+    /// reverbs[2].set_mode(FreeverbMode::Frozen);
+    /// assert!(reverbs[1].is_frozen() && reverbs[2].is_frozen());
+    ///
+    /// // The active tail decays, while the held and frozen tails sustain.
+    /// let mut energy = [0.0; 3];
+    /// for n in 0..96_000 {
+    ///     for (reverb, energy) in reverbs.iter_mut().zip(energy.iter_mut()) {
+    ///         let out = reverb.tick(&[0.0, 0.0]);
+    ///         if n >= 91_200 {
+    ///             *energy += out[0] * out[0];
+    ///         }
+    ///     }
+    /// }
+    /// assert!(energy[0] < 1e-3);
+    /// assert!(energy[1] > 10.0 && energy[2] > 10.0);
+    /// ```
+    pub fn set_hold(&mut self, val: bool) {
+        self.hold = val;
+    }
+
+    /// Whether the reverb is currently frozen, or fading into the freeze.
+    pub fn is_frozen(&self) -> bool {
+        self.freeze_target() == 1.0
+    }
+
+    pub fn set_freeze_fade(&mut self, val: f32) {
+        self.parameters.freeze_fade = val;
+        let samples = val * 0.001 * self.sample_rate as f32;
+        self.freeze_step = 1.0 / samples.max(1.0);
+    }
+
+    pub fn set_input(&mut self, val: FreeverbInput) {
+        self.parameters.input = val;
+    }
+
+    pub fn set_cross_feed(&mut self, val: f32) {
+        self.parameters.cross_feed = val;
+    }
+
+    /// Set the delay in milliseconds before the input reaches the reverb, limited to `N` samples.
+    ///
+    /// ```
+    /// use dspkit::effects::Freeverb;
+    ///
+    /// // Sample at which the reverb of an impulse starts.
+    /// let onset = |predelay| {
+    ///     let mut reverb = Freeverb::<f32, 4096>::default();
+    ///     reverb.prepare(48_000);
+    ///     reverb.set_predelay(predelay);
+    ///     (0..10_000)
+    ///         .position(|n| reverb.tick(&[if n == 0 { 1.0 } else { 0.0 }; 2])[0] != 0.0)
+    ///         .unwrap()
+    /// };
+    ///
+    /// assert_eq!(onset(50.0), onset(0.0) + 2_400);
+    /// ```
+    pub fn set_predelay(&mut self, val: f32) {
+        self.parameters.predelay = val;
+        for line in self.predelay.iter_mut() {
+            line.set_length(val.max(0.0) * 0.001, self.sample_rate);
+        }
+    }
+
+    pub fn set_size(&mut self, val: f32) {
+        self.parameters.size = val.max(0.0);
+        self.update_delays();
+    }
+
+    pub fn set_spread(&mut self, val: f32) {
+        self.parameters.spread = val.max(0.0);
+        self.update_delays();
+    }
+
+    pub fn set_mod_rate(&mut self, val: f32) {
+        self.parameters.mod_rate = val;
+        for (idx, lfo) in self.lfos.iter_mut().enumerate() {
+            let offset = idx as f32 / tuning::NUM_COMBS as f32 - 0.5;
+            lfo.set_frequency(val * (1.0 + tuning::MOD_RATE_SPREAD * offset));
+        }
+    }
+
+    /// Set the peak modulation of the comb lengths in milliseconds, which also updates the delay
+    /// times to leave room for the modulation.
+    pub fn set_mod_depth(&mut self, val: f32) {
+        self.parameters.mod_depth = val.max(0.0);
+        self.excursion = self.parameters.mod_depth * 0.001 * self.sample_rate as f32;
+        self.update_delays();
+    }
+
+    pub fn set_input_hpf(&mut self, val: f32) {
+        self.parameters.input_hpf = val;
+        for filter in self.input_hpf.iter_mut() {
+            set_highpass(filter, val, self.sample_rate);
+        }
+    }
+
+    pub fn set_input_lpf(&mut self, val: f32) {
+        self.parameters.input_lpf = val;
+        for filter in self.input_lpf.iter_mut() {
+            set_lowpass(filter, val, self.sample_rate);
+        }
+    }
+
+    pub fn set_output_hpf(&mut self, val: f32) {
+        self.parameters.output_hpf = val;
+        for filter in self.output_hpf.iter_mut() {
+            set_highpass(filter, val, self.sample_rate);
+        }
+    }
+
+    pub fn set_output_lpf(&mut self, val: f32) {
+        self.parameters.output_lpf = val;
+        for filter in self.output_lpf.iter_mut() {
+            set_lowpass(filter, val, self.sample_rate);
+        }
+    }
+
     /// Reset the freeverb filter by resetting all of the internal filters.
     pub fn reset(&mut self) -> &mut Self {
         for comb in self.combs_l.iter_mut() {
@@ -222,6 +538,18 @@ impl<S: PCM, const N: usize> Freeverb<S, N> {
         for allpass in self.allpass_r.iter_mut() {
             allpass.reset();
         }
+        for line in self.predelay.iter_mut() {
+            line.reset();
+        }
+        for filter in self
+            .input_hpf
+            .iter_mut()
+            .chain(self.input_lpf.iter_mut())
+            .chain(self.output_hpf.iter_mut())
+            .chain(self.output_lpf.iter_mut())
+        {
+            filter.reset();
+        }
         self
     }
 }
@@ -230,11 +558,23 @@ impl FreeverbParameters {
     pub const fn const_default() -> Self {
         FreeverbParameters {
             mode: FreeverbMode::Active,
+            freeze_fade: tuning::INITIAL_FREEZE_FADE_MS,
+            input: FreeverbInput::Mono,
+            cross_feed: tuning::INITIAL_CROSS_FEED,
             room_size: tuning::INITIAL_ROOM,
             damp: tuning::INITIAL_DAMP,
             wet: tuning::INITIAL_WET,
             dry: tuning::INITIAL_DRY,
             width: tuning::INITIAL_WIDTH,
+            predelay: tuning::INITIAL_PREDELAY_MS,
+            size: tuning::INITIAL_SIZE,
+            spread: tuning::INITIAL_SPREAD,
+            mod_rate: tuning::INITIAL_MOD_RATE,
+            mod_depth: tuning::INITIAL_MOD_DEPTH_MS,
+            input_hpf: 0.0,
+            input_lpf: 0.0,
+            output_hpf: 0.0,
+            output_lpf: 0.0,
         }
     }
 }
@@ -252,23 +592,34 @@ impl<S: PCM, const N: usize> Default for Freeverb<S, N> {
 }
 
 /// Compute derived variables used internally by the freeverb algorithm.
+///
+/// The values are those of the active reverb. The freeze is blended in per sample by
+/// [`Freeverb::tick`].
 const fn compute_derived_parameters(parameters: FreeverbParameters) -> FreeverbDerivedVars {
-    match parameters.mode {
-        FreeverbMode::Active => FreeverbDerivedVars {
-            gain: tuning::FIXED_GAIN,
-            wet_l: tuning::SCALE_WET * parameters.wet * (1.0 + parameters.width) * 0.5,
-            wet_r: tuning::SCALE_WET * parameters.wet * (1.0 - parameters.width) * 0.5,
-            dry: tuning::SCALE_DRY * parameters.dry,
-            room_size: parameters.room_size * tuning::SCALE_ROOM + tuning::OFFSET_ROOM,
-            damp: parameters.damp * tuning::SCALE_DAMP,
-        },
-        FreeverbMode::Frozen => FreeverbDerivedVars {
-            gain: 0.0,
-            wet_l: tuning::SCALE_WET * parameters.wet * (1.0 + parameters.width) * 0.5,
-            wet_r: tuning::SCALE_WET * parameters.wet * (1.0 - parameters.width) * 0.5,
-            dry: tuning::SCALE_DRY * parameters.dry,
-            room_size: 1.0,
-            damp: 0.0,
-        },
+    FreeverbDerivedVars {
+        gain: tuning::FIXED_GAIN,
+        wet_l: tuning::SCALE_WET * parameters.wet * (1.0 + parameters.width) * 0.5,
+        wet_r: tuning::SCALE_WET * parameters.wet * (1.0 - parameters.width) * 0.5,
+        dry: tuning::SCALE_DRY * parameters.dry,
+        room_size: parameters.room_size * tuning::SCALE_ROOM + tuning::OFFSET_ROOM,
+        damp: parameters.damp * tuning::SCALE_DAMP,
+    }
+}
+
+/// Configure a high-pass tone filter, bypassing it when the cutoff is 0.0.
+fn set_highpass(filter: &mut Biquad, cutoff: f32, sample_rate: usize) {
+    if cutoff > 0.0 {
+        filter.set_highpass(cutoff, tuning::TONE_Q, sample_rate);
+    } else {
+        filter.set_bypass();
+    }
+}
+
+/// Configure a low-pass tone filter, bypassing it when the cutoff is 0.0.
+fn set_lowpass(filter: &mut Biquad, cutoff: f32, sample_rate: usize) {
+    if cutoff > 0.0 {
+        filter.set_lowpass(cutoff, tuning::TONE_Q, sample_rate);
+    } else {
+        filter.set_bypass();
     }
 }
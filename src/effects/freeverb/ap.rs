@@ -26,7 +26,7 @@ impl<S: PCM, const N: usize> AllPass<S, N> {
 
         // update delay line
         let delay_input = input + delay_line * ALLPASS_FEEDBACK;
-        self.line.write(S::from(delay_input));
+        self.line.write_f32(delay_input);
         self.line.advance();
 
         delay_line - input
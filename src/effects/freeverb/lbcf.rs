@@ -1,30 +1,35 @@
 use crate::PCM;
 use crate::components::DelayLine;
 
+use libm::ceilf;
+
 /// Lowpass feedback comb filter with a maximum of `N` samples in the delay line.
 ///
 /// The delay line is lowpass-filtered and summed with the input signal.
-/// The low-pass filtering is a unity-gain one-pole low-pass.
+/// The low-pass filtering is a unity-gain one-pole low-pass. The delay length can be modulated
+/// within the excursion reserved by [`Comb::set_delay`].
 /// A complete analysis can be found [here](https://www.dsprelated.com/freebooks/pasp/Freeverb.html)
 #[derive(Debug, Copy, Clone)]
 pub struct Comb<S: PCM, const N: usize> {
     mix: f32,
     feedback: f32,
     lp_signal: S,
+    length: f32,
     line: DelayLine<S, N>,
 }
 
 impl<S: PCM, const N: usize> Comb<S, N> {
+    /// Process a sample, offsetting the delay length by `modulation` samples.
     #[inline(always)]
-    pub fn tick(&mut self, input: &f32) -> f32 {
-        let output: f32 = self.line.peek().into();
+    pub fn tick(&mut self, input: &f32, modulation: f32) -> f32 {
+        let output = self.line.tap_fractional(self.length + modulation);
 
         // Update using unity-gain one-pole lowpass filter on output signal.
         let lp_signal = self.mix * self.lp_signal.into() + (1.0 - self.mix) * output;
-        self.lp_signal = S::from(lp_signal);
+        self.lp_signal = self.line.encode(lp_signal);
 
         // Update delay line
-        self.line.write(S::from(input + self.feedback * lp_signal));
+        self.line.write_f32(input + self.feedback * lp_signal);
         self.line.advance();
 
         output
@@ -36,6 +41,7 @@ impl<S: PCM, const N: usize> Comb<S, N> {
             mix: 0.0,
             feedback: 0.0,
             lp_signal: S::PCM_EQUILIBRIUM,
+            length: N as f32,
             line: DelayLine::const_default(),
         }
     }
@@ -52,8 +58,13 @@ impl<S: PCM, const N: usize> Comb<S, N> {
         self.feedback = feedback;
     }
 
-    pub fn set_delay(&mut self, seconds: f32, sample_rate: usize) {
-        self.line.set_length(seconds, sample_rate);
+    /// Set the delay in seconds, reserving room in the delay line for a modulation of up to
+    /// `excursion` samples.
+    pub fn set_delay(&mut self, seconds: f32, excursion: f32, sample_rate: usize) {
+        let reserved = ceilf(excursion.max(0.0)) as usize + 1;
+        let length = ((seconds * sample_rate as f32) as usize).min(N.saturating_sub(reserved));
+        self.length = length as f32;
+        self.line.resize(length + reserved);
     }
 
     /// Reset the comb filter by clearing the underlying delay line.
@@ -23,8 +23,31 @@ pub const SCALE_DRY: f32 = 2.0;
 
 pub const INITIAL_WIDTH: f32 = 0.0; // 0.0 = mono, 1.0 = stereo
 
-// Spread between left and right delay times for stereo effect
+// Time to fade into and out of the freeze, avoiding a click from the change of feedback
+pub const INITIAL_FREEZE_FADE_MS: f32 = 50.0;
+
+// Amount of the opposite channel fed into each side with stereo input. 0.0 = independent channels,
+// 1.0 = mono sum
+pub const INITIAL_CROSS_FEED: f32 = 0.0;
+
+// Spread between left and right delay times for stereo effect, scaled by the stereo spread and
+// the size
 pub const STEREO_SPREAD_SEC: f32 = 0.000_521_541_9; // 23 samples (at 44.1kHz)
+pub const INITIAL_SPREAD: f32 = 1.0;
+
+// Scale of all of the delay times
+pub const INITIAL_SIZE: f32 = 1.0;
+
+pub const INITIAL_PREDELAY_MS: f32 = 0.0;
+
+// Slow modulation of the comb lengths, disabled by default. The rate of each comb is offset by a
+// fraction of the rate so the combs drift independently.
+pub const INITIAL_MOD_RATE: f32 = 0.3;
+pub const INITIAL_MOD_DEPTH_MS: f32 = 0.0;
+pub const MOD_RATE_SPREAD: f32 = 0.3;
+
+// Quality factor of the tone filters (Butterworth)
+pub const TONE_Q: f32 = 0.707;
 
 pub const NUM_COMBS: usize = 8;
 pub const COMB_SECOND_TUNINGS: [f32; NUM_COMBS] = [
@@ -0,0 +1,216 @@
+use crate::Stereo;
+use crate::components::{FirstOrderAllPass, Lfo, LfoShape};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Phaser built from `STAGES` cascaded first-order allpass filters per channel.
+///
+/// The break frequency of every stage is swept by an LFO around a center frequency. Mixing the
+/// phase-shifted signal with the dry input creates moving notches, and feeding the output of the
+/// last stage back into the first deepens them.
+///
+/// ```
+/// use dspkit::effects::Phaser;
+///
+/// static PHASER: Phaser<6> = Phaser::const_default();
+///
+/// let mut phaser = Phaser::<6>::default();
+///
+/// // A notch sweeps across a fixed tone over a cycle of the LFO, so its level rises and falls.
+/// let mut power = [0.0; 20];
+/// for n in 0..96_000 {
+///     let x = libm::sinf(2.0 * core::f32::consts::PI * 1_000.0 * n as f32 / 48_000.0);
+///     let out = phaser.tick(&[x, x]);
+///     power[n / 4_800] += out[0] * out[0] / 4_800.0;
+/// }
+/// let max = power.iter().copied().fold(0.0, f32::max);
+/// let min = power.iter().copied().fold(f32::MAX, f32::min);
+/// assert!(min < 0.1 * max);
+/// ```
+pub struct Phaser<const STAGES: usize> {
+    parameters: PhaserParameters,
+    sample_rate: usize,
+    left: PhaserChannel<STAGES>,
+    right: PhaserChannel<STAGES>,
+}
+
+/// Parameters to the phaser effect.
+#[derive(Clone, Copy)]
+pub struct PhaserParameters {
+    /// Rate of the LFO sweep in Hz.
+    pub rate: f32,
+    /// Center break frequency of the allpass stages in Hz.
+    pub center: f32,
+    /// Sweep depth in Hz. The break frequency moves within `center ± depth`.
+    pub depth: f32,
+    /// Amount of the last stage fed back into the first. Valid range is -0.99 to 0.99.
+    pub feedback: f32,
+    /// Phase offset between the left and right LFOs in cycles. 0.0 = in phase to 0.5 = opposite
+    /// phase.
+    pub stereo_phase: f32,
+    /// Mix of the phase-shifted signal. 0.0 = dry to 1.0 = full wet, where 0.5 produces the
+    /// deepest notches.
+    pub mix: f32,
+}
+
+impl<const STAGES: usize> Phaser<STAGES> {
+    /// Construct a phaser effect with the given initial parameters. The feedback is clamped as in
+    /// [`Phaser::set_feedback`].
+    ///
+    /// ```
+    /// use dspkit::effects::{Phaser, PhaserParameters};
+    ///
+    /// let mut phaser = Phaser::<6>::new(PhaserParameters {
+    ///     feedback: 2.0,
+    ///     ..PhaserParameters::default()
+    /// });
+    ///
+    /// // Feedback beyond the valid range would make the allpass loop unstable.
+    /// for n in 0..96_000 {
+    ///     let x = libm::sinf(0.1 * n as f32);
+    ///     assert!(phaser.tick(&[x, x])[0].abs() < 100.0);
+    /// }
+    /// ```
+    pub fn new(parameters: PhaserParameters) -> Self {
+        let mut phaser = Self::const_default();
+        phaser.parameters = parameters;
+        phaser.set_rate(parameters.rate);
+        phaser.set_feedback(parameters.feedback);
+        phaser.set_stereo_phase(parameters.stereo_phase);
+        phaser
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        let parameters = PhaserParameters::const_default();
+        let mut right = PhaserChannel::const_default(parameters.rate);
+        right.lfo.set_phase(parameters.stereo_phase);
+        Self {
+            parameters,
+            sample_rate: INITIAL_SAMPLE_RATE,
+            left: PhaserChannel::const_default(parameters.rate),
+            right,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.left.lfo.prepare(sample_rate);
+        self.right.lfo.prepare(sample_rate);
+        self.set_feedback(self.parameters.feedback);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let wet_l = self.left.tick(input[0], &self.parameters, self.sample_rate);
+        let wet_r = self
+            .right
+            .tick(input[1], &self.parameters, self.sample_rate);
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (wet_l - input[0]),
+            input[1] + mix * (wet_r - input[1]),
+        ]
+    }
+
+    pub fn set_rate(&mut self, val: f32) {
+        self.parameters.rate = val;
+        self.left.lfo.set_frequency(val);
+        self.right.lfo.set_frequency(val);
+    }
+
+    pub fn set_center(&mut self, val: f32) {
+        self.parameters.center = val;
+    }
+
+    pub fn set_depth(&mut self, val: f32) {
+        self.parameters.depth = val;
+    }
+
+    pub fn set_feedback(&mut self, val: f32) {
+        self.parameters.feedback = val.clamp(-0.99, 0.99);
+    }
+
+    /// Set the phase offset of the right LFO relative to the left LFO, in cycles.
+    pub fn set_stereo_phase(&mut self, val: f32) {
+        self.parameters.stereo_phase = val;
+        self.right.lfo.set_phase(self.left.lfo.phase() + val);
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Reset the phaser by clearing the allpass stages and restarting the LFOs.
+    pub fn reset(&mut self) -> &mut Self {
+        self.left.reset();
+        self.right.reset();
+        self.right.lfo.set_phase(self.parameters.stereo_phase);
+        self
+    }
+}
+
+/// Allpass cascade and LFO for a single channel.
+struct PhaserChannel<const STAGES: usize> {
+    stages: [FirstOrderAllPass; STAGES],
+    lfo: Lfo,
+    last: f32,
+}
+
+impl<const STAGES: usize> PhaserChannel<STAGES> {
+    const fn const_default(rate: f32) -> Self {
+        Self {
+            stages: [FirstOrderAllPass::const_default(); STAGES],
+            lfo: Lfo::new(LfoShape::Sine, rate, INITIAL_SAMPLE_RATE),
+            last: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    fn tick(&mut self, input: f32, parameters: &PhaserParameters, sample_rate: usize) -> f32 {
+        let frequency = parameters.center + parameters.depth * self.lfo.tick();
+        let coefficient = FirstOrderAllPass::coefficient(frequency, sample_rate);
+
+        let mut out = input + parameters.feedback * self.last;
+        for stage in self.stages.iter_mut() {
+            stage.set_coefficient(coefficient);
+            out = stage.tick(&out);
+        }
+        self.last = out;
+
+        out
+    }
+
+    fn reset(&mut self) {
+        for stage in self.stages.iter_mut() {
+            stage.reset();
+        }
+        self.lfo.reset();
+        self.last = 0.0;
+    }
+}
+
+impl PhaserParameters {
+    pub const fn const_default() -> Self {
+        PhaserParameters {
+            rate: 0.5,
+            center: 1_000.0,
+            depth: 800.0,
+            feedback: 0.5,
+            stereo_phase: 0.25,
+            mix: 0.5,
+        }
+    }
+}
+
+impl Default for PhaserParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const STAGES: usize> Default for Phaser<STAGES> {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
@@ -0,0 +1,214 @@
+use crate::components::DelayLine;
+use crate::{AudioNode, PCM, Stereo};
+
+use core::f32::consts::PI;
+use libm::{exp2f, floorf, sinf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Real-time pitch shifter, reading a delay line with two crossfaded read heads.
+///
+/// Each of the internal delay lines are limited to a maximum of `N` samples, which bounds the
+/// window size.
+///
+/// A read head whose delay changes at a constant rate plays the input back at a different speed,
+/// and therefore at a different pitch. The delay of each head sweeps across the window and jumps
+/// back when it reaches the end, with the heads half a window apart. The heads are crossfaded so
+/// that each one is silent while it jumps. Shorter windows lower the latency and suit percussive
+/// material, while longer windows reduce the roughness of the crossfades on sustained notes.
+///
+/// Unlike a spectral pitch shifter, the delay stays within the window, which keeps the latency
+/// low enough for harmonizers and octave effects played live.
+///
+/// ```
+/// use dspkit::effects::{PitchShifter, PitchShifterParameters};
+///
+/// static SHIFTER: PitchShifter<f32, 4096> = PitchShifter::const_default();
+///
+/// let mut shifter = PitchShifter::<f32, 4096>::new(PitchShifterParameters {
+///     semitones: 12.0,
+///     ..PitchShifterParameters::default()
+/// });
+///
+/// // An octave up doubles the number of zero crossings of a sine, whose period of 360 samples
+/// // divides half of the window so that the heads are in phase while crossfading.
+/// let (mut input, mut output) = (0, 0);
+/// let (mut last_in, mut last_out) = (0.0, 0.0);
+/// for n in 0..48_000 {
+///     let x = libm::sinf(core::f32::consts::PI / 180.0 * n as f32);
+///     let y = shifter.tick(&[x, x])[0];
+///     if n >= 4_800 {
+///         input += (x * last_in < 0.0) as usize;
+///         output += (y * last_out < 0.0) as usize;
+///     }
+///     (last_in, last_out) = (x, y);
+/// }
+/// assert!((output as f32 / input as f32 - 2.0).abs() < 0.01);
+/// ```
+pub struct PitchShifter<S: PCM, const N: usize> {
+    parameters: PitchShifterParameters,
+    sample_rate: usize,
+    left: DelayLine<S, N>,
+    right: DelayLine<S, N>,
+    /// Window size in samples.
+    window: f32,
+    /// Position of the first head within the window, 0.0 to 1.0.
+    phase: f32,
+    /// Change of the phase per sample.
+    step: f32,
+}
+
+/// Parameters to the pitch shifter effect.
+#[derive(Clone, Copy)]
+pub struct PitchShifterParameters {
+    /// Pitch shift in semitones, e.g. 12.0 for an octave up and -12.0 for an octave down.
+    pub semitones: f32,
+    /// Fine pitch shift in cents, added to the semitones.
+    pub cents: f32,
+    /// Window size in milliseconds, i.e. the range of delays swept by the read heads.
+    pub window: f32,
+    /// Mix of the shifted signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+impl<S: PCM, const N: usize> PitchShifter<S, N> {
+    /// Construct a pitch shifter effect with the given initial parameters.
+    pub fn new(parameters: PitchShifterParameters) -> Self {
+        let mut shifter = Self::const_default();
+        shifter.parameters = parameters;
+        shifter.prepare(INITIAL_SAMPLE_RATE);
+        shifter
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The window and pitch ratio
+    /// are computed in [`PitchShifter::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: PitchShifterParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            left: DelayLine::const_default(),
+            right: DelayLine::const_default(),
+            window: 0.0,
+            phase: 0.0,
+            step: 0.0,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.set_window(self.parameters.window);
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.left.write_f32(input[0]);
+        self.right.write_f32(input[1]);
+
+        // The heads are half a window apart, and each fades out as it jumps across the window.
+        let phase_a = self.phase;
+        let phase_b = wrap(phase_a + 0.5);
+        let delay_a = 1.0 + phase_a * self.window;
+        let delay_b = 1.0 + phase_b * self.window;
+        let gain_a = sinf(PI * phase_a);
+        let gain_a = gain_a * gain_a;
+        let gain_b = 1.0 - gain_a;
+
+        let wet_l =
+            gain_a * self.left.tap_fractional(delay_a) + gain_b * self.left.tap_fractional(delay_b);
+        let wet_r = gain_a * self.right.tap_fractional(delay_a)
+            + gain_b * self.right.tap_fractional(delay_b);
+        self.left.advance();
+        self.right.advance();
+        self.phase = wrap(self.phase + self.step);
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (wet_l - input[0]),
+            input[1] + mix * (wet_r - input[1]),
+        ]
+    }
+
+    pub fn set_semitones(&mut self, val: f32) {
+        self.parameters.semitones = val;
+        self.update_step();
+    }
+
+    pub fn set_cents(&mut self, val: f32) {
+        self.parameters.cents = val;
+        self.update_step();
+    }
+
+    /// Set the window size in milliseconds, clamped to fit within the capacity of the delay lines.
+    pub fn set_window(&mut self, val: f32) {
+        self.parameters.window = val;
+        let max_window = (N as f32 - 2.0).max(0.0);
+        self.window = (val * 0.001 * self.sample_rate as f32).clamp(0.0, max_window);
+        self.update_step();
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Current pitch ratio between the shifted signal and the input.
+    pub fn ratio(&self) -> f32 {
+        exp2f((self.parameters.semitones + 0.01 * self.parameters.cents) / 12.0)
+    }
+
+    /// Reset the pitch shifter by clearing the delay lines and restarting the read heads.
+    pub fn reset(&mut self) -> &mut Self {
+        self.left.reset();
+        self.right.reset();
+        self.phase = 0.0;
+        self
+    }
+
+    /// The delay of a head must fall by `ratio - 1` samples per sample to play back at the ratio,
+    /// i.e. the phase moves by that fraction of the window.
+    fn update_step(&mut self) {
+        self.step = if self.window > 0.0 {
+            (1.0 - self.ratio()) / self.window
+        } else {
+            0.0
+        };
+    }
+}
+
+impl PitchShifterParameters {
+    pub const fn const_default() -> Self {
+        PitchShifterParameters {
+            semitones: 0.0,
+            cents: 0.0,
+            window: 30.0,
+            mix: 1.0,
+        }
+    }
+}
+
+impl Default for PitchShifterParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const N: usize> Default for PitchShifter<S, N> {
+    fn default() -> Self {
+        Self::new(PitchShifterParameters::const_default())
+    }
+}
+
+impl<S: PCM, const N: usize> AudioNode<Stereo<f32>, Stereo<f32>> for PitchShifter<S, N> {
+    fn prepare(&mut self, sample_rate: usize) {
+        PitchShifter::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        PitchShifter::tick(self, input)
+    }
+}
+
+/// Wrap a phase into the range 0.0 to 1.0.
+#[inline(always)]
+fn wrap(phase: f32) -> f32 {
+    phase - floorf(phase)
+}
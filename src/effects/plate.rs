@@ -0,0 +1,403 @@
+mod diffuser;
+mod tuning;
+
+use crate::components::{DelayLine, Lfo, LfoShape};
+use crate::{AudioNode, PCM, Stereo};
+use diffuser::Diffuser;
+use tuning::Node;
+
+use libm::{ceilf, roundf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Implementation of the plate reverb by Jon Dattorro.
+///
+/// Each of the internal delay lines are limited to a maximum of `N` samples. At 48 kHz, the
+/// original size of the plate requires 8192 samples, and larger sizes or sample rates require
+/// proportionally more.
+///
+/// The input is summed to mono, delayed, band-limited and smeared by four allpass diffusers before
+/// entering the tank: two halves of allpasses and delays feeding each other in a loop, with
+/// damping and decay applied on every trip. The first allpass of each half is modulated, which
+/// keeps the tank from ringing at fixed frequencies. The stereo output is tapped from several
+/// points of both halves, which keeps the tail dense and decorrelated between the channels.
+///
+/// A complete description of the algorithm can be found in "Effect Design, Part 1: Reverberator
+/// and Other Filters" (1997).
+///
+/// ```
+/// use dspkit::effects::{Plate, PlateParameters};
+///
+/// static PLATE: Plate<f32, 8192> = Plate::const_default();
+///
+/// // Energy of the impulse response in windows of half a second.
+/// fn energy(decay: f32, damping: f32) -> [f32; 4] {
+///     let mut plate = Plate::<f32, 8192>::new(PlateParameters {
+///         decay,
+///         damping,
+///         mix: 1.0,
+///         ..PlateParameters::default()
+///     });
+///     let mut energy = [0.0; 4];
+///     for n in 0..96_000 {
+///         let x = if n == 0 { 1.0 } else { 0.0 };
+///         let out = plate.tick(&[x, x]);
+///         energy[n / 24_000] += out[0] * out[0] + out[1] * out[1];
+///     }
+///     energy
+/// }
+///
+/// // The tail dies away, more slowly with a longer decay and more quickly with more damping.
+/// let short = energy(0.5, 0.0005);
+/// assert!(short.windows(2).all(|pair| pair[1] < 0.1 * pair[0]));
+/// let long = energy(0.8, 0.0005);
+/// assert!(long[2] > 10.0 * short[2]);
+/// let damped = energy(0.8, 0.5);
+/// assert!(damped[2] < 0.5 * long[2]);
+/// ```
+pub struct Plate<S: PCM, const N: usize> {
+    parameters: PlateParameters,
+    sample_rate: usize,
+    decay_diffusion: f32,
+    excursion: f32,
+    predelay: DelayLine<S, N>,
+    bandwidth: f32,
+    diffusers: [Diffuser<S, N>; tuning::NUM_INPUT_DIFFUSERS],
+    tank_l: PlateTank<S, N>,
+    tank_r: PlateTank<S, N>,
+    taps_l: [usize; tuning::NUM_TAPS],
+    taps_r: [usize; tuning::NUM_TAPS],
+}
+
+/// Parameters to the plate reverb effect.
+#[derive(Clone, Copy)]
+pub struct PlateParameters {
+    /// Delay in milliseconds before the input reaches the plate.
+    pub predelay: f32,
+    /// Bandwidth of the input low-pass filter. 0.0 = dark to 1.0 = unfiltered.
+    pub bandwidth: f32,
+    /// Amount of damping applied to high frequencies in the tank. 0.0 = no damping to 1.0 = full
+    /// damping.
+    pub damping: f32,
+    /// Gain applied on every trip around the tank. 0.0 = short to 1.0 = infinite tail.
+    pub decay: f32,
+    /// Scale of the delay lengths relative to the original plate. 1.0 = original size.
+    pub size: f32,
+    /// Depth of the modulation of the tank. 0.0 = no modulation to 1.0 = original excursion.
+    pub modulation: f32,
+    /// Mix of the reverb signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+impl<S: PCM, const N: usize> Plate<S, N> {
+    /// Construct a plate reverb effect with the given initial parameters.
+    ///
+    /// ```
+    /// use dspkit::effects::{Plate, PlateParameters};
+    ///
+    /// let plate = Plate::<f32, 8192>::new(PlateParameters {
+    ///     predelay: 20.0,
+    ///     bandwidth: 0.9995,
+    ///     damping: 0.0005,
+    ///     decay: 0.5,
+    ///     size: 1.0,
+    ///     modulation: 1.0,
+    ///     mix: 0.3,
+    /// });
+    /// ```
+    pub fn new(parameters: PlateParameters) -> Self {
+        let mut plate = Self::const_default();
+        plate.parameters = parameters;
+        plate.prepare(INITIAL_SAMPLE_RATE);
+        plate
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The delay lengths are
+    /// computed in [`Plate::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: PlateParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            decay_diffusion: 0.0,
+            excursion: 0.0,
+            predelay: DelayLine::const_default(),
+            bandwidth: 0.0,
+            diffusers: [Diffuser::const_default(); tuning::NUM_INPUT_DIFFUSERS],
+            tank_l: PlateTank::const_default(),
+            tank_r: PlateTank::const_default(),
+            taps_l: [1; tuning::NUM_TAPS],
+            taps_r: [1; tuning::NUM_TAPS],
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+
+        for (diffuser, coefficient) in self.diffusers.iter_mut().zip(tuning::INPUT_DIFFUSION) {
+            diffuser.set_coefficient(coefficient);
+        }
+        self.tank_l.prepare(sample_rate);
+        self.tank_r.prepare(sample_rate);
+        // The halves of the tank are modulated in quadrature.
+        self.tank_r.lfo.set_phase(0.25);
+
+        self.set_predelay(self.parameters.predelay);
+        self.set_decay(self.parameters.decay);
+        self.set_size(self.parameters.size);
+        self.set_modulation(self.parameters.modulation);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        let mono = 0.5 * (input[0] + input[1]);
+
+        let delayed = if self.predelay.is_empty() {
+            mono
+        } else {
+            let delayed = self.predelay.peek().into();
+            self.predelay.write_f32(mono);
+            self.predelay.advance();
+            delayed
+        };
+
+        self.bandwidth += self.parameters.bandwidth * (delayed - self.bandwidth);
+        let diffused = self
+            .diffusers
+            .iter_mut()
+            .fold(self.bandwidth, |acc, diffuser| diffuser.tick(acc));
+
+        // Each half of the tank is fed by the output of the other half.
+        let decay = self.parameters.decay;
+        let feedback_l = self.tank_r.output();
+        let feedback_r = self.tank_l.output();
+
+        let damping = self.parameters.damping;
+        let diffusion = self.decay_diffusion;
+        let excursion = self.excursion;
+        self.tank_l.tick(
+            diffused + decay * feedback_l,
+            decay,
+            damping,
+            diffusion,
+            excursion,
+        );
+        self.tank_r.tick(
+            diffused + decay * feedback_r,
+            decay,
+            damping,
+            diffusion,
+            excursion,
+        );
+
+        let wet_l = self.output(&tuning::TAPS_L, &self.taps_l);
+        let wet_r = self.output(&tuning::TAPS_R, &self.taps_r);
+
+        let mix = self.parameters.mix;
+        [
+            input[0] + mix * (wet_l - input[0]),
+            input[1] + mix * (wet_r - input[1]),
+        ]
+    }
+
+    /// Sum the output taps of a channel.
+    #[inline(always)]
+    fn output(
+        &self,
+        taps: &[(Node, f32, f32); tuning::NUM_TAPS],
+        delays: &[usize; tuning::NUM_TAPS],
+    ) -> f32 {
+        let sum: f32 = taps
+            .iter()
+            .zip(delays)
+            .map(|((node, _, sign), delay)| {
+                let val: f32 = match node {
+                    Node::DelayL1 => self.tank_l.delay_1.tap(*delay).into(),
+                    Node::DiffuserL => self.tank_l.diffuser.tap(*delay),
+                    Node::DelayL2 => self.tank_l.delay_2.tap(*delay).into(),
+                    Node::DelayR1 => self.tank_r.delay_1.tap(*delay).into(),
+                    Node::DiffuserR => self.tank_r.diffuser.tap(*delay),
+                    Node::DelayR2 => self.tank_r.delay_2.tap(*delay).into(),
+                };
+                sign * val
+            })
+            .sum();
+        tuning::OUTPUT_GAIN * sum
+    }
+
+    pub fn set_predelay(&mut self, val: f32) {
+        self.parameters.predelay = val;
+        self.predelay
+            .set_length(val.max(0.0) * 0.001, self.sample_rate);
+    }
+
+    pub fn set_bandwidth(&mut self, val: f32) {
+        self.parameters.bandwidth = val.clamp(0.0, 1.0);
+    }
+
+    pub fn set_damping(&mut self, val: f32) {
+        self.parameters.damping = val.clamp(0.0, 1.0);
+    }
+
+    pub fn set_decay(&mut self, val: f32) {
+        self.parameters.decay = val.clamp(0.0, 1.0);
+        self.decay_diffusion = (self.parameters.decay + tuning::DECAY_DIFFUSION_2_OFFSET)
+            .clamp(tuning::DECAY_DIFFUSION_2_MIN, tuning::DECAY_DIFFUSION_2_MAX);
+    }
+
+    /// Set the scale of the delay lengths. The lengths are clamped to the capacity of the
+    /// internal delay lines.
+    pub fn set_size(&mut self, val: f32) {
+        self.parameters.size = val.max(0.0);
+
+        let rate = self.sample_rate as f32 / tuning::REFERENCE_SAMPLE_RATE;
+        let scale = rate * self.parameters.size;
+        let length = |reference: f32| (roundf(reference * scale) as usize).max(1);
+
+        for (diffuser, reference) in self
+            .diffusers
+            .iter_mut()
+            .zip(tuning::INPUT_DIFFUSER_LENGTHS)
+        {
+            diffuser.set_delay(length(reference));
+        }
+
+        let max_excursion = tuning::EXCURSION * rate;
+        self.tank_l
+            .set_lengths(tuning::TANK_LENGTHS_L.map(length), max_excursion);
+        self.tank_r
+            .set_lengths(tuning::TANK_LENGTHS_R.map(length), max_excursion);
+
+        self.taps_l = tuning::TAPS_L.map(|(_, reference, _)| length(reference));
+        self.taps_r = tuning::TAPS_R.map(|(_, reference, _)| length(reference));
+    }
+
+    pub fn set_modulation(&mut self, val: f32) {
+        self.parameters.modulation = val.clamp(0.0, 1.0);
+        let rate = self.sample_rate as f32 / tuning::REFERENCE_SAMPLE_RATE;
+        self.excursion = self.parameters.modulation * tuning::EXCURSION * rate;
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Reset the plate by clearing all of the internal delay lines and filters.
+    pub fn reset(&mut self) -> &mut Self {
+        self.predelay.reset();
+        self.bandwidth = 0.0;
+        for diffuser in self.diffusers.iter_mut() {
+            diffuser.reset();
+        }
+        self.tank_l.reset();
+        self.tank_r.reset();
+        self
+    }
+}
+
+impl PlateParameters {
+    pub const fn const_default() -> Self {
+        PlateParameters {
+            predelay: 0.0,
+            bandwidth: 0.9995,
+            damping: 0.0005,
+            decay: 0.5,
+            size: 1.0,
+            modulation: 1.0,
+            mix: 0.3,
+        }
+    }
+}
+
+impl Default for PlateParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const N: usize> Default for Plate<S, N> {
+    fn default() -> Self {
+        Self::new(PlateParameters::const_default())
+    }
+}
+
+impl<S: PCM, const N: usize> AudioNode<Stereo<f32>, Stereo<f32>> for Plate<S, N> {
+    fn prepare(&mut self, sample_rate: usize) {
+        Plate::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        Plate::tick(self, input)
+    }
+}
+
+/// One half of the tank: a modulated allpass, a delay, damping, an allpass and a second delay.
+struct PlateTank<S: PCM, const N: usize> {
+    modulated: Diffuser<S, N>,
+    modulated_length: f32,
+    delay_1: DelayLine<S, N>,
+    damping: f32,
+    diffuser: Diffuser<S, N>,
+    delay_2: DelayLine<S, N>,
+    lfo: Lfo,
+}
+
+impl<S: PCM, const N: usize> PlateTank<S, N> {
+    const fn const_default() -> Self {
+        Self {
+            modulated: Diffuser::const_default(),
+            modulated_length: 1.0,
+            delay_1: DelayLine::const_default(),
+            damping: 0.0,
+            diffuser: Diffuser::const_default(),
+            delay_2: DelayLine::const_default(),
+            lfo: Lfo::new(LfoShape::Sine, tuning::MODULATION_RATE, INITIAL_SAMPLE_RATE),
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: usize) {
+        self.modulated.set_coefficient(tuning::DECAY_DIFFUSION_1);
+        self.lfo.prepare(sample_rate);
+    }
+
+    /// Output of the second delay, which feeds the other half of the tank.
+    #[inline(always)]
+    fn output(&self) -> f32 {
+        self.delay_2.peek().into()
+    }
+
+    #[inline(always)]
+    fn tick(&mut self, input: f32, decay: f32, damping: f32, diffusion: f32, excursion: f32) {
+        let delay = self.modulated_length + excursion * self.lfo.tick();
+        let diffused = self.modulated.tick_modulated(input, delay);
+
+        let delayed: f32 = self.delay_1.peek().into();
+        self.delay_1.write_f32(diffused);
+        self.delay_1.advance();
+
+        self.damping += (1.0 - damping) * (delayed - self.damping);
+
+        self.diffuser.set_coefficient(diffusion);
+        let diffused = self.diffuser.tick(decay * self.damping);
+
+        self.delay_2.write_f32(diffused);
+        self.delay_2.advance();
+    }
+
+    /// Set the lengths of the allpasses and delays in samples, leaving room in the modulated
+    /// allpass for the maximum excursion.
+    fn set_lengths(&mut self, lengths: [usize; 4], max_excursion: f32) {
+        self.modulated_length = lengths[0] as f32;
+        self.modulated
+            .set_delay(lengths[0] + ceilf(max_excursion) as usize + 1);
+        self.delay_1.resize(lengths[1]);
+        self.diffuser.set_delay(lengths[2]);
+        self.delay_2.resize(lengths[3]);
+    }
+
+    fn reset(&mut self) {
+        self.modulated.reset();
+        self.delay_1.reset();
+        self.damping = 0.0;
+        self.diffuser.reset();
+        self.delay_2.reset();
+    }
+}
@@ -0,0 +1,65 @@
+use crate::PCM;
+use crate::components::DelayLine;
+
+/// Schroeder allpass filter with a maximum of `N` samples in the delay line, used to diffuse the
+/// input and the tank of the plate.
+///
+/// Unlike the allpass approximation of Freeverb, the feed-forward and feedback gains match so the
+/// filter is a true allpass for any coefficient.
+#[derive(Debug, Copy, Clone)]
+pub struct Diffuser<S: PCM, const N: usize> {
+    line: DelayLine<S, N>,
+    coefficient: f32,
+}
+
+impl<S: PCM, const N: usize> Diffuser<S, N> {
+    /// Default const constructor, i.e. can be created at compile-time.
+    pub const fn const_default() -> Self {
+        Self {
+            line: DelayLine::const_default(),
+            coefficient: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let delayed: f32 = self.line.peek().into();
+        self.process(input, delayed)
+    }
+
+    /// Process a sample with a fractional delay in samples, shorter than the length of the delay
+    /// line.
+    #[inline(always)]
+    pub fn tick_modulated(&mut self, input: f32, delay: f32) -> f32 {
+        let delayed = self.line.tap_fractional(delay);
+        self.process(input, delayed)
+    }
+
+    #[inline(always)]
+    fn process(&mut self, input: f32, delayed: f32) -> f32 {
+        let feedback = input - self.coefficient * delayed;
+        self.line.write_f32(feedback);
+        self.line.advance();
+        delayed + self.coefficient * feedback
+    }
+
+    /// Read the internal delay line `delay` samples ago.
+    #[inline(always)]
+    pub fn tap(&self, delay: usize) -> f32 {
+        self.line.tap(delay).into()
+    }
+
+    pub fn set_coefficient(&mut self, coefficient: f32) {
+        self.coefficient = coefficient;
+    }
+
+    /// Set the delay in samples.
+    pub fn set_delay(&mut self, samples: usize) {
+        self.line.resize(samples);
+    }
+
+    /// Reset the allpass filter by clearing the underlying delay line.
+    pub fn reset(&mut self) {
+        self.line.reset();
+    }
+}
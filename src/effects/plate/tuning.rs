@@ -0,0 +1,66 @@
+// Plate tuning from: Jon Dattorro, "Effect Design, Part 1: Reverberator and Other Filters", 1997.
+// Delay lengths are given in samples at the reference sample rate of the original design.
+
+pub const REFERENCE_SAMPLE_RATE: f32 = 29_761.0;
+
+pub const OUTPUT_GAIN: f32 = 0.6;
+
+pub const NUM_INPUT_DIFFUSERS: usize = 4;
+pub const INPUT_DIFFUSER_LENGTHS: [f32; NUM_INPUT_DIFFUSERS] = [142.0, 107.0, 379.0, 277.0];
+pub const INPUT_DIFFUSION: [f32; NUM_INPUT_DIFFUSERS] = [0.75, 0.75, 0.625, 0.625];
+
+// The modulated allpass at the start of each half of the tank has the opposite sign to the other
+// diffusers.
+pub const DECAY_DIFFUSION_1: f32 = -0.7;
+
+// The second allpass of each half of the tank follows the decay, within these bounds.
+pub const DECAY_DIFFUSION_2_OFFSET: f32 = 0.15;
+pub const DECAY_DIFFUSION_2_MIN: f32 = 0.25;
+pub const DECAY_DIFFUSION_2_MAX: f32 = 0.5;
+
+// Peak excursion of the modulated allpasses, and the rate of their modulation in Hz.
+pub const EXCURSION: f32 = 16.0;
+pub const MODULATION_RATE: f32 = 1.0;
+
+// Lengths of each half of the tank: modulated allpass, delay, allpass, delay.
+pub const TANK_LENGTHS_L: [f32; 4] = [672.0, 4453.0, 1800.0, 3720.0];
+pub const TANK_LENGTHS_R: [f32; 4] = [908.0, 4217.0, 2656.0, 3163.0];
+
+/// Node of the tank to read an output tap from.
+#[derive(Clone, Copy)]
+pub enum Node {
+    /// First delay of the left half of the tank.
+    DelayL1,
+    /// Second allpass of the left half of the tank.
+    DiffuserL,
+    /// Second delay of the left half of the tank.
+    DelayL2,
+    /// First delay of the right half of the tank.
+    DelayR1,
+    /// Second allpass of the right half of the tank.
+    DiffuserR,
+    /// Second delay of the right half of the tank.
+    DelayR2,
+}
+
+pub const NUM_TAPS: usize = 7;
+
+// Output taps of each channel as the node, delay in samples and sign.
+pub const TAPS_L: [(Node, f32, f32); NUM_TAPS] = [
+    (Node::DelayR1, 266.0, 1.0),
+    (Node::DelayR1, 2974.0, 1.0),
+    (Node::DiffuserR, 1913.0, -1.0),
+    (Node::DelayR2, 1996.0, 1.0),
+    (Node::DelayL1, 1990.0, -1.0),
+    (Node::DiffuserL, 187.0, -1.0),
+    (Node::DelayL2, 1066.0, -1.0),
+];
+pub const TAPS_R: [(Node, f32, f32); NUM_TAPS] = [
+    (Node::DelayL1, 353.0, 1.0),
+    (Node::DelayL1, 3627.0, 1.0),
+    (Node::DiffuserL, 1228.0, -1.0),
+    (Node::DelayL2, 2673.0, 1.0),
+    (Node::DelayR1, 2111.0, -1.0),
+    (Node::DiffuserR, 335.0, -1.0),
+    (Node::DelayR2, 121.0, -1.0),
+];
@@ -0,0 +1,75 @@
+mod blur;
+mod denoise;
+mod freeze;
+
+pub use blur::*;
+pub use denoise::*;
+pub use freeze::*;
+
+use crate::Stereo;
+use crate::components::{SpectralProcessor, Stft};
+use crate::fft::Window;
+
+/// STFTs processing both channels of a stereo signal with a Hann window, and a delay of the dry
+/// signal matching their latency.
+struct StereoStft<P: SpectralProcessor<N>, const N: usize, const HOP: usize> {
+    stfts: [Stft<P, N, HOP>; 2],
+    dry: [[Stereo<f32>; N]; 2],
+    position: usize,
+}
+
+impl<P: SpectralProcessor<N>, const N: usize, const HOP: usize> StereoStft<P, N, HOP> {
+    const fn new(left: P, right: P) -> Self {
+        Self {
+            stfts: [
+                Stft::new(left, Window::Hann),
+                Stft::new(right, Window::Hann),
+            ],
+            dry: [[[0.0; 2]; N]; 2],
+            position: 0,
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: usize) {
+        for stft in self.stfts.iter_mut() {
+            stft.prepare(sample_rate);
+        }
+    }
+
+    /// Process a frame, mixing the processed signal with the delayed dry signal.
+    #[inline]
+    fn tick(&mut self, input: &Stereo<f32>, mix: f32) -> Stereo<f32> {
+        let wet = [self.stfts[0].tick(input[0]), self.stfts[1].tick(input[1])];
+
+        // The frame written a whole buffer less one sample ago is next in the buffer.
+        let dry = self.dry.as_flattened_mut();
+        dry[self.position] = *input;
+        self.position = (self.position + 1) % dry.len();
+        let dry = dry[self.position];
+
+        [
+            dry[0] + mix * (wet[0] - dry[0]),
+            dry[1] + mix * (wet[1] - dry[1]),
+        ]
+    }
+
+    fn processors_mut(&mut self) -> impl Iterator<Item = &mut P> {
+        self.stfts.iter_mut().map(|stft| stft.processor_mut())
+    }
+
+    fn random_phase_gain(&self) -> f32 {
+        self.stfts[0].random_phase_gain()
+    }
+
+    fn latency(&self) -> usize {
+        self.stfts[0].latency()
+    }
+
+    fn reset(&mut self) {
+        for stft in self.stfts.iter_mut() {
+            stft.reset();
+        }
+        self.dry = [[[0.0; 2]; N]; 2];
+        self.position = 0;
+    }
+}
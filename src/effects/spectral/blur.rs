@@ -0,0 +1,207 @@
+use super::StereoStft;
+use crate::components::{SpectralProcessor, WhiteNoise};
+use crate::fft::Complex;
+use crate::{AudioNode, Stereo};
+
+use core::f32::consts::TAU;
+use libm::expf;
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Seeds of the phases of silent bins of the left and right channels.
+const SEEDS: [u32; 2] = [0x0B1A_8B1A, 0x5EED_B1A2];
+
+/// Spectral blur effect, smearing the spectrum over time.
+///
+/// The magnitude of each bin is smoothed by a one-pole filter across the spectra, while the phase
+/// of the current spectrum is kept. Short times soften transients, and long times turn the input
+/// into a slowly evolving wash which keeps sounding after the input stops. Bins which are silent in
+/// the current spectrum have no phase, and are given a random phase instead.
+///
+/// The spectra have `N` bins from frames of `2 * N` samples, with a new frame every `HOP` samples.
+/// The output is delayed by the length of a frame, less one sample.
+///
+/// ```
+/// use dspkit::effects::{SpectralBlur, SpectralBlurParameters};
+///
+/// static BLUR: SpectralBlur<1024, 512> = SpectralBlur::const_default();
+///
+/// let mut blur = SpectralBlur::<256, 128>::new(SpectralBlurParameters {
+///     time: 200.0,
+///     mix: 1.0,
+/// });
+///
+/// // Mean power of the output over windows of 100 ms, with a sine stopping after 1 s.
+/// let mut power = [0.0; 20];
+/// for n in 0..96_000 {
+///     let x = if n < 48_000 { libm::sinf(0.05 * n as f32) } else { 0.0 };
+///     let out = blur.tick(&[x, x]);
+///     power[n / 4_800] += out[0] * out[0] / 4_800.0;
+/// }
+///
+/// // A steady sine passes at about its level, with a mean power of 0.5.
+/// assert!((power[9] - 0.5).abs() < 0.05);
+/// // After the input stops, the output decays over the time rather than cutting off.
+/// assert!(power[11] > 0.01 && power[11] < 0.5 * power[10]);
+/// assert!(power[15] < 0.002);
+/// ```
+pub struct SpectralBlur<const N: usize, const HOP: usize> {
+    parameters: SpectralBlurParameters,
+    sample_rate: usize,
+    stft: StereoStft<BlurProcessor<N>, N, HOP>,
+}
+
+/// Parameters to the spectral blur effect.
+#[derive(Clone, Copy)]
+pub struct SpectralBlurParameters {
+    /// Time constant in milliseconds of the smoothing of the magnitudes.
+    pub time: f32,
+    /// Mix of the processed signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+/// Smoothed magnitudes of a single channel.
+struct BlurProcessor<const N: usize> {
+    /// Smoothed magnitudes of the bins `0..N`.
+    magnitudes: [f32; N],
+    nyquist: f32,
+    /// Weight of the current spectrum in the smoothing.
+    coefficient: f32,
+    noise: WhiteNoise,
+}
+
+impl<const N: usize, const HOP: usize> SpectralBlur<N, HOP> {
+    /// Construct a spectral blur effect with the given initial parameters.
+    pub fn new(parameters: SpectralBlurParameters) -> Self {
+        let mut blur = Self::const_default();
+        blur.parameters = parameters;
+        blur.prepare(INITIAL_SAMPLE_RATE);
+        blur
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The windows and smoothing
+    /// are computed in [`SpectralBlur::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: SpectralBlurParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            stft: StereoStft::new(BlurProcessor::new(SEEDS[0]), BlurProcessor::new(SEEDS[1])),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.stft.prepare(sample_rate);
+        self.set_time(self.parameters.time);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.stft.tick(input, self.parameters.mix)
+    }
+
+    pub fn set_time(&mut self, val: f32) {
+        self.parameters.time = val;
+        let frames = val * 0.001 * self.sample_rate as f32 / HOP as f32;
+        let coefficient = if frames > 0.0 {
+            1.0 - expf(-1.0 / frames)
+        } else {
+            1.0
+        };
+        for processor in self.stft.processors_mut() {
+            processor.coefficient = coefficient;
+        }
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Delay in samples of the output, which is the length of a frame less one sample.
+    pub fn latency(&self) -> usize {
+        self.stft.latency()
+    }
+
+    /// Reset the effect by clearing the frames and the smoothed magnitudes.
+    pub fn reset(&mut self) -> &mut Self {
+        self.stft.reset();
+        for (processor, seed) in self.stft.processors_mut().zip(SEEDS) {
+            processor.magnitudes = [0.0; N];
+            processor.nyquist = 0.0;
+            processor.noise.reseed(seed);
+        }
+        self
+    }
+}
+
+impl<const N: usize> BlurProcessor<N> {
+    const fn new(seed: u32) -> Self {
+        Self {
+            magnitudes: [0.0; N],
+            nyquist: 0.0,
+            coefficient: 1.0,
+            noise: WhiteNoise::new(seed),
+        }
+    }
+}
+
+impl<const N: usize> SpectralProcessor<N> for BlurProcessor<N> {
+    fn process(&mut self, spectrum: &mut [Complex; N]) {
+        let coefficient = self.coefficient;
+
+        // The DC and Nyquist bins are real, so only their magnitudes are smoothed.
+        let (dc, nyquist) = (spectrum[0].re, spectrum[0].im);
+        self.magnitudes[0] += coefficient * (dc.abs() - self.magnitudes[0]);
+        self.nyquist += coefficient * (nyquist.abs() - self.nyquist);
+        spectrum[0] = Complex::new(
+            self.magnitudes[0].copysign(dc),
+            self.nyquist.copysign(nyquist),
+        );
+
+        for (bin, magnitude) in spectrum[1..].iter_mut().zip(&mut self.magnitudes[1..]) {
+            let norm = bin.norm();
+            *magnitude += coefficient * (norm - *magnitude);
+            *bin = if norm > 0.0 {
+                bin.scale(*magnitude / norm)
+            } else {
+                Complex::from_polar(*magnitude, TAU * self.noise.next_unipolar())
+            };
+        }
+    }
+}
+
+impl SpectralBlurParameters {
+    pub const fn const_default() -> Self {
+        SpectralBlurParameters {
+            time: 500.0,
+            mix: 1.0,
+        }
+    }
+}
+
+impl Default for SpectralBlurParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const N: usize, const HOP: usize> Default for SpectralBlur<N, HOP> {
+    fn default() -> Self {
+        Self::new(SpectralBlurParameters::const_default())
+    }
+}
+
+impl<const N: usize, const HOP: usize> AudioNode<Stereo<f32>, Stereo<f32>>
+    for SpectralBlur<N, HOP>
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        SpectralBlur::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        SpectralBlur::tick(self, input)
+    }
+
+    fn latency(&self) -> usize {
+        SpectralBlur::latency(self)
+    }
+}
@@ -0,0 +1,293 @@
+use super::StereoStft;
+use crate::components::SpectralProcessor;
+use crate::fft::Complex;
+use crate::{AudioNode, Stereo, db_to_gain};
+
+use libm::expf;
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Spectral noise reduction effect, attenuating a learned noise profile.
+///
+/// While learning, the input passes through unprocessed and the power of each bin is averaged
+/// into the noise profile of each channel. Afterwards, each bin is attenuated by a Wiener-like
+/// gain `1 - reduction * noise / power`, which removes steady noise such as hiss and hum while
+/// keeping the bins where the signal dominates. The gains are limited by the spectral floor, and
+/// smoothed over time to reduce the "musical noise" of gains fluctuating between frames.
+///
+/// The spectra have `N` bins from frames of `2 * N` samples, with a new frame every `HOP` samples.
+/// The output is delayed by the length of a frame, less one sample.
+///
+/// ```
+/// use dspkit::effects::{SpectralDenoise, SpectralDenoiseParameters};
+/// use dspkit::components::WhiteNoise;
+///
+/// static DENOISE: SpectralDenoise<1024, 512> = SpectralDenoise::const_default();
+///
+/// let mut denoise = SpectralDenoise::<256, 128>::new(SpectralDenoiseParameters {
+///     learn: true,
+///     ..SpectralDenoiseParameters::default()
+/// });
+/// let mut noise = WhiteNoise::default();
+///
+/// // Learn the profile of the noise, which is then attenuated down to the floor.
+/// for _ in 0..10_000 {
+///     let x = 0.1 * noise.tick();
+///     denoise.tick(&[x, x]);
+/// }
+/// denoise.set_learn(false);
+/// let (mut input, mut output) = (0.0, 0.0);
+/// for n in 0..20_000 {
+///     let x = 0.1 * noise.tick();
+///     let out = denoise.tick(&[x, x]);
+///     if n >= 10_000 {
+///         input += x * x;
+///         output += out[0] * out[0];
+///     }
+/// }
+/// assert!(output < 0.1 * input);
+/// ```
+pub struct SpectralDenoise<const N: usize, const HOP: usize> {
+    parameters: SpectralDenoiseParameters,
+    sample_rate: usize,
+    stft: StereoStft<DenoiseProcessor<N>, N, HOP>,
+}
+
+/// Parameters to the spectral noise reduction effect.
+#[derive(Clone, Copy)]
+pub struct SpectralDenoiseParameters {
+    /// Learn the noise profile from the input, which passes through unprocessed. Starting to learn
+    /// discards the previous profile.
+    pub learn: bool,
+    /// Amount of the noise profile removed from each bin. 0.0 = none, 1.0 = the learned noise, and
+    /// above 1.0 removes more noise at the cost of more artifacts.
+    pub reduction: f32,
+    /// Spectral floor in decibels, i.e. the largest attenuation of a bin.
+    pub floor: f32,
+    /// Time constant in milliseconds of the smoothing of the gains.
+    pub smoothing: f32,
+    /// Mix of the processed signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+/// Noise profile and gains of a single channel.
+struct DenoiseProcessor<const N: usize> {
+    learn: bool,
+    /// Number of spectra averaged into the profile.
+    frames: usize,
+    /// Average power of the noise in the bins `0..N`.
+    noise: [f32; N],
+    noise_nyquist: f32,
+    /// Smoothed gains of the bins `0..N`.
+    gains: [f32; N],
+    gain_nyquist: f32,
+    reduction: f32,
+    floor: f32,
+    /// Weight of the current gains in the smoothing.
+    coefficient: f32,
+}
+
+impl<const N: usize, const HOP: usize> SpectralDenoise<N, HOP> {
+    /// Construct a spectral noise reduction effect with the given initial parameters.
+    pub fn new(parameters: SpectralDenoiseParameters) -> Self {
+        let mut denoise = Self::const_default();
+        denoise.parameters = parameters;
+        denoise.prepare(INITIAL_SAMPLE_RATE);
+        denoise
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The windows and smoothing
+    /// are computed in [`SpectralDenoise::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: SpectralDenoiseParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            stft: StereoStft::new(DenoiseProcessor::new(), DenoiseProcessor::new()),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.stft.prepare(sample_rate);
+        self.set_learn(self.parameters.learn);
+        self.set_reduction(self.parameters.reduction);
+        self.set_floor(self.parameters.floor);
+        self.set_smoothing(self.parameters.smoothing);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.stft.tick(input, self.parameters.mix)
+    }
+
+    /// Start or stop learning the noise profile. Starting to learn discards the previous profile.
+    pub fn set_learn(&mut self, val: bool) {
+        self.parameters.learn = val;
+        for processor in self.stft.processors_mut() {
+            if val && !processor.learn {
+                processor.frames = 0;
+            }
+            processor.learn = val;
+        }
+    }
+
+    pub fn set_reduction(&mut self, val: f32) {
+        self.parameters.reduction = val;
+        for processor in self.stft.processors_mut() {
+            processor.reduction = val.max(0.0);
+        }
+    }
+
+    pub fn set_floor(&mut self, val: f32) {
+        self.parameters.floor = val;
+        let floor = db_to_gain(val.min(0.0));
+        for processor in self.stft.processors_mut() {
+            processor.floor = floor;
+        }
+    }
+
+    pub fn set_smoothing(&mut self, val: f32) {
+        self.parameters.smoothing = val;
+        let frames = val * 0.001 * self.sample_rate as f32 / HOP as f32;
+        let coefficient = if frames > 0.0 {
+            1.0 - expf(-1.0 / frames)
+        } else {
+            1.0
+        };
+        for processor in self.stft.processors_mut() {
+            processor.coefficient = coefficient;
+        }
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Delay in samples of the output, which is the length of a frame less one sample.
+    pub fn latency(&self) -> usize {
+        self.stft.latency()
+    }
+
+    /// Reset the effect by clearing the frames and the smoothed gains. The noise profile is kept.
+    pub fn reset(&mut self) -> &mut Self {
+        self.stft.reset();
+        for processor in self.stft.processors_mut() {
+            processor.gains = [1.0; N];
+            processor.gain_nyquist = 1.0;
+        }
+        self
+    }
+}
+
+impl<const N: usize> DenoiseProcessor<N> {
+    const fn new() -> Self {
+        Self {
+            learn: false,
+            frames: 0,
+            noise: [0.0; N],
+            noise_nyquist: 0.0,
+            gains: [1.0; N],
+            gain_nyquist: 1.0,
+            reduction: 1.0,
+            floor: 0.0,
+            coefficient: 1.0,
+        }
+    }
+}
+
+impl<const N: usize> SpectralProcessor<N> for DenoiseProcessor<N> {
+    fn process(&mut self, spectrum: &mut [Complex; N]) {
+        // The DC and Nyquist bins are real and packed into the first bin, so they are handled
+        // separately from the other bins.
+        let (dc, nyquist) = (spectrum[0].re, spectrum[0].im);
+
+        if self.learn {
+            // Cumulative average of the power of all of the spectra since learning started.
+            self.frames += 1;
+            let weight = 1.0 / self.frames as f32;
+            self.noise[0] += weight * (dc * dc - self.noise[0]);
+            self.noise_nyquist += weight * (nyquist * nyquist - self.noise_nyquist);
+            for (noise, bin) in self.noise[1..].iter_mut().zip(&spectrum[1..]) {
+                *noise += weight * (bin.norm_sqr() - *noise);
+            }
+            return;
+        }
+
+        if self.frames == 0 {
+            return;
+        }
+
+        let coefficient = self.coefficient;
+        let target = wiener_gain(dc * dc, self.noise[0], self.reduction, self.floor);
+        self.gains[0] += coefficient * (target - self.gains[0]);
+        let target = wiener_gain(
+            nyquist * nyquist,
+            self.noise_nyquist,
+            self.reduction,
+            self.floor,
+        );
+        self.gain_nyquist += coefficient * (target - self.gain_nyquist);
+        spectrum[0] = Complex::new(self.gains[0] * dc, self.gain_nyquist * nyquist);
+
+        let (reduction, floor) = (self.reduction, self.floor);
+        for ((bin, noise), gain) in spectrum[1..]
+            .iter_mut()
+            .zip(&self.noise[1..])
+            .zip(&mut self.gains[1..])
+        {
+            let target = wiener_gain(bin.norm_sqr(), *noise, reduction, floor);
+            *gain += coefficient * (target - *gain);
+            *bin = bin.scale(*gain);
+        }
+    }
+}
+
+impl SpectralDenoiseParameters {
+    pub const fn const_default() -> Self {
+        SpectralDenoiseParameters {
+            learn: false,
+            reduction: 1.0,
+            floor: -20.0,
+            smoothing: 30.0,
+            mix: 1.0,
+        }
+    }
+}
+
+impl Default for SpectralDenoiseParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const N: usize, const HOP: usize> Default for SpectralDenoise<N, HOP> {
+    fn default() -> Self {
+        Self::new(SpectralDenoiseParameters::const_default())
+    }
+}
+
+impl<const N: usize, const HOP: usize> AudioNode<Stereo<f32>, Stereo<f32>>
+    for SpectralDenoise<N, HOP>
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        SpectralDenoise::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        SpectralDenoise::tick(self, input)
+    }
+
+    fn latency(&self) -> usize {
+        SpectralDenoise::latency(self)
+    }
+}
+
+/// Gain of a bin given its power and the power of the noise, limited by the floor.
+#[inline(always)]
+fn wiener_gain(power: f32, noise: f32, reduction: f32, floor: f32) -> f32 {
+    let gain = if power > 0.0 {
+        1.0 - reduction * noise / power
+    } else {
+        0.0
+    };
+    gain.max(floor)
+}
@@ -0,0 +1,248 @@
+use super::StereoStft;
+use crate::components::{SpectralProcessor, WhiteNoise};
+use crate::fft::Complex;
+use crate::{AudioNode, Stereo};
+
+use core::f32::consts::TAU;
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Seeds of the random phases of the left and right channels.
+const SEEDS: [u32; 2] = [0x5FEC_F2EE, 0x0C0F_FEE5];
+
+/// Spectral freeze effect, sustaining a captured spectrum indefinitely.
+///
+/// When frozen, the magnitudes of the next spectrum of each channel are captured, and every
+/// following spectrum is resynthesized from the captured magnitudes with random phases. Unlike a
+/// reverb with infinite feedback, the sustain has no resonances or modulation, and the phases of
+/// the channels are independent, which gives a wide stereo image. The effect crossfades between
+/// the live and frozen spectra over the fade time, in both directions.
+///
+/// The spectra have `N` bins from frames of `2 * N` samples, with a new frame every `HOP` samples.
+/// The output is delayed by the length of a frame, less one sample.
+///
+/// ```
+/// use dspkit::effects::{SpectralFreeze, SpectralFreezeParameters};
+///
+/// static FREEZE: SpectralFreeze<1024, 512> = SpectralFreeze::const_default();
+///
+/// let mut freeze = SpectralFreeze::<256, 128>::new(SpectralFreezeParameters {
+///     mix: 1.0,
+///     ..SpectralFreezeParameters::default()
+/// });
+///
+/// // Capture a sine, then sustain it after the input stops.
+/// for n in 0..4_000 {
+///     let x = libm::sinf(0.1 * n as f32);
+///     freeze.tick(&[x, x]);
+/// }
+/// freeze.set_frozen(true);
+/// let mut energy = 0.0;
+/// for n in 0..8_000 {
+///     let x = if n < 1_000 { libm::sinf(0.1 * n as f32) } else { 0.0 };
+///     let out = freeze.tick(&[x, x]);
+///     if n >= 4_000 {
+///         energy += out[0] * out[0];
+///     }
+/// }
+/// assert!(energy > 100.0);
+/// ```
+pub struct SpectralFreeze<const N: usize, const HOP: usize> {
+    parameters: SpectralFreezeParameters,
+    sample_rate: usize,
+    stft: StereoStft<FreezeProcessor<N>, N, HOP>,
+}
+
+/// Parameters to the spectral freeze effect.
+#[derive(Clone, Copy)]
+pub struct SpectralFreezeParameters {
+    /// Sustain the spectrum captured when frozen.
+    pub frozen: bool,
+    /// Time in milliseconds to crossfade between the live and frozen spectra.
+    pub fade: f32,
+    /// Mix of the processed signal. 0.0 = dry to 1.0 = full wet.
+    pub mix: f32,
+}
+
+/// Captured magnitudes and crossfade of a single channel.
+struct FreezeProcessor<const N: usize> {
+    frozen: bool,
+    captured: bool,
+    /// Captured magnitudes of the bins `0..N`.
+    magnitudes: [f32; N],
+    nyquist: f32,
+    /// Current crossfade, 0.0 = live to 1.0 = frozen.
+    amount: f32,
+    step: f32,
+    gain: f32,
+    noise: WhiteNoise,
+}
+
+impl<const N: usize, const HOP: usize> SpectralFreeze<N, HOP> {
+    /// Construct a spectral freeze effect with the given initial parameters.
+    pub fn new(parameters: SpectralFreezeParameters) -> Self {
+        let mut freeze = Self::const_default();
+        freeze.parameters = parameters;
+        freeze.prepare(INITIAL_SAMPLE_RATE);
+        freeze
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The windows and fade are
+    /// computed in [`SpectralFreeze::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        Self {
+            parameters: SpectralFreezeParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            stft: StereoStft::new(
+                FreezeProcessor::new(SEEDS[0]),
+                FreezeProcessor::new(SEEDS[1]),
+            ),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.stft.prepare(sample_rate);
+        let gain = self.stft.random_phase_gain();
+        for processor in self.stft.processors_mut() {
+            processor.gain = gain;
+        }
+        self.set_frozen(self.parameters.frozen);
+        self.set_fade(self.parameters.fade);
+    }
+
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        self.stft.tick(input, self.parameters.mix)
+    }
+
+    /// Freeze the spectrum at the next frame, or release the frozen spectrum.
+    pub fn set_frozen(&mut self, val: bool) {
+        self.parameters.frozen = val;
+        for processor in self.stft.processors_mut() {
+            processor.frozen = val;
+        }
+    }
+
+    pub fn set_fade(&mut self, val: f32) {
+        self.parameters.fade = val;
+        let frames = val * 0.001 * self.sample_rate as f32 / HOP as f32;
+        for processor in self.stft.processors_mut() {
+            processor.step = 1.0 / frames.max(1.0);
+        }
+    }
+
+    pub fn set_mix(&mut self, val: f32) {
+        self.parameters.mix = val;
+    }
+
+    /// Delay in samples of the output, which is the length of a frame less one sample.
+    pub fn latency(&self) -> usize {
+        self.stft.latency()
+    }
+
+    /// Reset the effect by clearing the frames and the captured spectrum.
+    pub fn reset(&mut self) -> &mut Self {
+        self.stft.reset();
+        for (processor, seed) in self.stft.processors_mut().zip(SEEDS) {
+            processor.captured = false;
+            processor.amount = 0.0;
+            processor.noise.reseed(seed);
+        }
+        self
+    }
+}
+
+impl<const N: usize> FreezeProcessor<N> {
+    const fn new(seed: u32) -> Self {
+        Self {
+            frozen: false,
+            captured: false,
+            magnitudes: [0.0; N],
+            nyquist: 0.0,
+            amount: 0.0,
+            step: 1.0,
+            gain: 1.0,
+            noise: WhiteNoise::new(seed),
+        }
+    }
+}
+
+impl<const N: usize> SpectralProcessor<N> for FreezeProcessor<N> {
+    fn process(&mut self, spectrum: &mut [Complex; N]) {
+        if self.frozen && !self.captured {
+            self.captured = true;
+            self.magnitudes[0] = spectrum[0].re.abs();
+            self.nyquist = spectrum[0].im.abs();
+            for (magnitude, bin) in self.magnitudes[1..].iter_mut().zip(&spectrum[1..]) {
+                *magnitude = bin.norm();
+            }
+        }
+
+        if self.frozen {
+            self.amount = (self.amount + self.step).min(1.0);
+        } else {
+            self.amount = (self.amount - self.step).max(0.0);
+            if self.amount == 0.0 {
+                // Capture a new spectrum at the next freeze.
+                self.captured = false;
+                return;
+            }
+        }
+
+        // The DC and Nyquist bins are real, so only their signs are randomized.
+        let mut sign = || {
+            if self.noise.next_u32() & 1 == 0 {
+                1.0
+            } else {
+                -1.0
+            }
+        };
+        let (dc, nyquist) = (sign(), sign());
+        let frozen = Complex::new(dc * self.magnitudes[0], nyquist * self.nyquist);
+        spectrum[0] += (frozen.scale(self.gain) - spectrum[0]).scale(self.amount);
+
+        for (bin, magnitude) in spectrum[1..].iter_mut().zip(&self.magnitudes[1..]) {
+            let phase = TAU * self.noise.next_unipolar();
+            let frozen = Complex::from_polar(self.gain * magnitude, phase);
+            *bin += (frozen - *bin).scale(self.amount);
+        }
+    }
+}
+
+impl SpectralFreezeParameters {
+    pub const fn const_default() -> Self {
+        SpectralFreezeParameters {
+            frozen: false,
+            fade: 200.0,
+            mix: 1.0,
+        }
+    }
+}
+
+impl Default for SpectralFreezeParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const N: usize, const HOP: usize> Default for SpectralFreeze<N, HOP> {
+    fn default() -> Self {
+        Self::new(SpectralFreezeParameters::const_default())
+    }
+}
+
+impl<const N: usize, const HOP: usize> AudioNode<Stereo<f32>, Stereo<f32>>
+    for SpectralFreeze<N, HOP>
+{
+    fn prepare(&mut self, sample_rate: usize) {
+        SpectralFreeze::prepare(self, sample_rate);
+    }
+
+    fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        SpectralFreeze::tick(self, input)
+    }
+
+    fn latency(&self) -> usize {
+        SpectralFreeze::latency(self)
+    }
+}
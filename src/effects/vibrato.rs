@@ -0,0 +1,216 @@
+use crate::components::{DelayLine, Lfo, LfoShape};
+use crate::{PCM, Stereo};
+
+use core::f32::consts::PI;
+use libm::exp2f;
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Seeds for the random LFOs, chosen so that the channels wander independently.
+const SEED_L: u32 = 0x1234_5678;
+const SEED_R: u32 = 0x8765_4321;
+
+/// Vibrato effect, i.e. pitch modulation through a 100% wet modulated delay line.
+///
+/// Each of the internal delay lines are limited to a maximum of `N` samples, which bounds the
+/// depth available at low rates.
+///
+/// Since only the delayed signal is heard, the perceived effect is the rate of change of the
+/// delay rather than its absolute length. The depth is therefore given as the peak pitch
+/// deviation in cents, and converted into a delay excursion that depends on the LFO rate. The left
+/// and right channels have their own LFOs so they can be modulated independently.
+///
+/// ```
+/// use dspkit::effects::{Vibrato, VibratoParameters};
+///
+/// static VIBRATO: Vibrato<f32, 1024> = Vibrato::const_default();
+///
+/// let mut vibrato = Vibrato::<f32, 1024>::new(VibratoParameters {
+///     depth: 50.0,
+///     ..VibratoParameters::default()
+/// });
+///
+/// // The pitch of a tone, measured between upward zero crossings, deviates by the depth.
+/// let (mut last, mut crossing, mut up, mut down) = (0.0, None, 0.0_f32, 0.0_f32);
+/// for n in 0..48_000 {
+///     let x = libm::sinf(2.0 * core::f32::consts::PI * 1_000.0 * n as f32 / 48_000.0);
+///     let y = vibrato.tick(&[x, x])[0];
+///     if last < 0.0 && y >= 0.0 {
+///         let time = n as f32 - y / (y - last);
+///         if let Some(previous) = crossing.filter(|_| n >= 4_800) {
+///             let cents = 1_200.0 * libm::log2f(48.0 / (time - previous));
+///             (up, down) = (up.max(cents), down.min(cents));
+///         }
+///         crossing = Some(time);
+///     }
+///     last = y;
+/// }
+/// assert!((up - 50.0).abs() < 0.5);
+/// assert!((down + 50.0).abs() < 2.0);
+/// ```
+pub struct Vibrato<S: PCM, const N: usize> {
+    parameters: VibratoParameters,
+    sample_rate: usize,
+    excursion: f32,
+    left: DelayLine<S, N>,
+    right: DelayLine<S, N>,
+    lfo_l: Lfo,
+    lfo_r: Lfo,
+}
+
+/// Parameters to the vibrato effect.
+#[derive(Clone, Copy)]
+pub struct VibratoParameters {
+    /// Waveform of the modulation.
+    pub shape: LfoShape,
+    /// Rate of the modulation in Hz.
+    pub rate: f32,
+    /// Peak pitch deviation in cents. Exact for [`LfoShape::Sine`] and [`LfoShape::Triangle`], and
+    /// approximate for [`LfoShape::Random`].
+    pub depth: f32,
+    /// Phase offset between the left and right LFOs in cycles. 0.0 = in phase to 0.5 = opposite
+    /// phase.
+    pub stereo_phase: f32,
+}
+
+impl<S: PCM, const N: usize> Vibrato<S, N> {
+    /// Construct a vibrato effect with the given initial parameters.
+    pub fn new(parameters: VibratoParameters) -> Self {
+        let mut vibrato = Self::const_default();
+        vibrato.parameters = parameters;
+        vibrato.set_shape(parameters.shape);
+        vibrato.set_rate(parameters.rate);
+        vibrato.set_stereo_phase(parameters.stereo_phase);
+        vibrato
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The modulation depth is
+    /// computed in [`Vibrato::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        let parameters = VibratoParameters::const_default();
+        let mut lfo_l = Lfo::new(parameters.shape, parameters.rate, INITIAL_SAMPLE_RATE);
+        let mut lfo_r = Lfo::new(parameters.shape, parameters.rate, INITIAL_SAMPLE_RATE);
+        lfo_l.set_seed(SEED_L);
+        lfo_r.set_seed(SEED_R);
+        lfo_r.set_phase(parameters.stereo_phase);
+        Self {
+            parameters,
+            sample_rate: INITIAL_SAMPLE_RATE,
+            excursion: 0.0,
+            left: DelayLine::const_default(),
+            right: DelayLine::const_default(),
+            lfo_l,
+            lfo_r,
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.lfo_l.prepare(sample_rate);
+        self.lfo_r.prepare(sample_rate);
+        self.update_excursion();
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self, input: &Stereo<f32>) -> Stereo<f32> {
+        // Center the modulation so the delay never drops below a single sample.
+        let center = self.excursion + 1.0;
+
+        let out_l = self
+            .left
+            .tap_fractional(center + self.excursion * self.lfo_l.tick());
+        self.left.write_f32(input[0]);
+        self.left.advance();
+
+        let out_r = self
+            .right
+            .tap_fractional(center + self.excursion * self.lfo_r.tick());
+        self.right.write_f32(input[1]);
+        self.right.advance();
+
+        [out_l, out_r]
+    }
+
+    pub fn set_shape(&mut self, val: LfoShape) {
+        self.parameters.shape = val;
+        self.lfo_l.set_shape(val);
+        self.lfo_r.set_shape(val);
+        self.update_excursion();
+    }
+
+    pub fn set_rate(&mut self, val: f32) {
+        self.parameters.rate = val;
+        self.lfo_l.set_frequency(val);
+        self.lfo_r.set_frequency(val);
+        self.update_excursion();
+    }
+
+    pub fn set_depth(&mut self, val: f32) {
+        self.parameters.depth = val;
+        self.update_excursion();
+    }
+
+    /// Set the phase offset of the right LFO relative to the left LFO, in cycles.
+    pub fn set_stereo_phase(&mut self, val: f32) {
+        self.parameters.stereo_phase = val;
+        self.lfo_r.set_phase(self.lfo_l.phase() + val);
+    }
+
+    /// Reset the vibrato by clearing the delay lines and restarting the LFOs.
+    pub fn reset(&mut self) -> &mut Self {
+        self.left.reset();
+        self.right.reset();
+        self.lfo_l.reset();
+        self.lfo_r.reset();
+        self.lfo_r.set_phase(self.parameters.stereo_phase);
+        self
+    }
+
+    fn update_excursion(&mut self) {
+        self.excursion = compute_excursion(self.parameters, self.sample_rate, N);
+    }
+}
+
+impl VibratoParameters {
+    pub const fn const_default() -> Self {
+        VibratoParameters {
+            shape: LfoShape::Sine,
+            rate: 5.0,
+            depth: 25.0,
+            stereo_phase: 0.0,
+        }
+    }
+}
+
+impl Default for VibratoParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<S: PCM, const N: usize> Default for Vibrato<S, N> {
+    fn default() -> Self {
+        Self::new(VibratoParameters::const_default())
+    }
+}
+
+/// Compute the peak delay excursion in samples which produces the requested pitch deviation.
+///
+/// The pitch ratio of a modulated delay is `1 - d'(n)`, so the excursion is chosen such that the
+/// peak slope of the LFO waveform equals the ratio offset for the depth in cents. The excursion is
+/// clamped such that the full modulation fits within the `capacity` of the delay line.
+fn compute_excursion(parameters: VibratoParameters, sample_rate: usize, capacity: usize) -> f32 {
+    let max_excursion = (capacity as f32 - 2.0) * 0.5;
+    if parameters.depth <= 0.0 || parameters.rate <= 0.0 || max_excursion <= 0.0 {
+        return 0.0;
+    }
+
+    // Slope of a unit amplitude LFO, per sample.
+    let slope = match parameters.shape {
+        LfoShape::Triangle => 4.0 * parameters.rate / sample_rate as f32,
+        LfoShape::Sine | LfoShape::Random => 2.0 * PI * parameters.rate / sample_rate as f32,
+    };
+    let ratio = exp2f(parameters.depth / 1200.0);
+
+    ((ratio - 1.0) / slope).min(max_excursion)
+}
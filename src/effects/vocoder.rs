@@ -0,0 +1,256 @@
+use crate::components::{Biquad, EnvelopeFollower};
+use crate::{SidechainNode, Stereo, db_to_gain};
+
+use libm::{exp2f, powf, sqrtf};
+
+const INITIAL_SAMPLE_RATE: usize = 48_000;
+
+/// Channel vocoder with `BANDS` bands, imposing the spectral envelope of a modulator on a carrier.
+///
+/// Both signals are split by banks of band-pass filters, spaced logarithmically between the lowest
+/// and highest band frequencies. An envelope follower tracks the level of each modulator band and
+/// scales the matching carrier band, and the carrier bands are summed into the output. A voice as
+/// modulator and a rich carrier such as a sawtooth or noise give the classic talking synthesizer.
+///
+/// The formant shift moves the carrier bands relative to the modulator bands, which shifts the
+/// formants of the output up or down without changing the pitch of the carrier.
+///
+/// The vocoder takes two inputs, and is processed through [`Vocoder::tick`] or the
+/// [`SidechainNode`] implementation, with the carrier as input and the modulator as key.
+///
+/// ```
+/// use dspkit::effects::Vocoder;
+///
+/// static VOCODER: Vocoder<16> = Vocoder::const_default();
+///
+/// let mut vocoder = Vocoder::<16>::default();
+/// let carrier = |n: usize| libm::sinf(0.1 * n as f32);
+///
+/// // The carrier is only heard while the modulator has energy in the same bands.
+/// let mut energy = [0.0; 2];
+/// for n in 0..9_600 {
+///     let modulator = if n < 4_800 { 0.0 } else { carrier(n) };
+///     let out = vocoder.tick(&[carrier(n); 2], &[modulator; 2]);
+///     energy[n / 4_800] += out[0] * out[0];
+/// }
+/// assert!(energy[0] < 1e-6);
+/// assert!(energy[1] > 100.0);
+/// ```
+pub struct Vocoder<const BANDS: usize> {
+    parameters: VocoderParameters,
+    sample_rate: usize,
+    gain: f32,
+    left: VocoderChannel<BANDS>,
+    right: VocoderChannel<BANDS>,
+}
+
+/// Parameters to the vocoder effect.
+#[derive(Clone, Copy)]
+pub struct VocoderParameters {
+    /// Center frequency in Hz of the lowest band.
+    pub low: f32,
+    /// Center frequency in Hz of the highest band.
+    pub high: f32,
+    /// Quality factor of the band-pass filters. Higher values give narrower bands.
+    pub q: f32,
+    /// Time in milliseconds for the band envelopes to rise.
+    pub attack: f32,
+    /// Time in milliseconds for the band envelopes to fall.
+    pub release: f32,
+    /// Shift in semitones of the carrier bands relative to the modulator bands.
+    pub formant_shift: f32,
+    /// Gain in dB applied to the output.
+    pub gain: f32,
+}
+
+/// Filter banks and envelope followers for a single channel.
+struct VocoderChannel<const BANDS: usize> {
+    carrier: [Biquad; BANDS],
+    modulator: [Biquad; BANDS],
+    envelopes: [EnvelopeFollower; BANDS],
+}
+
+impl<const BANDS: usize> Vocoder<BANDS> {
+    /// Construct a vocoder effect with the given initial parameters.
+    pub fn new(parameters: VocoderParameters) -> Self {
+        let mut vocoder = Self::const_default();
+        vocoder.parameters = parameters;
+        vocoder.prepare(INITIAL_SAMPLE_RATE);
+        vocoder
+    }
+
+    /// Default const constructor, i.e. can be created at compile-time. The filters and envelopes
+    /// are computed in [`Vocoder::prepare`], which must be called before processing.
+    pub const fn const_default() -> Self {
+        const { assert!(BANDS >= 1, "vocoder needs at least one band") };
+
+        Self {
+            parameters: VocoderParameters::const_default(),
+            sample_rate: INITIAL_SAMPLE_RATE,
+            gain: 1.0,
+            left: VocoderChannel::const_default(),
+            right: VocoderChannel::const_default(),
+        }
+    }
+
+    pub fn prepare(&mut self, sample_rate: usize) {
+        self.sample_rate = sample_rate;
+        self.update_bands();
+        self.set_attack(self.parameters.attack);
+        self.set_release(self.parameters.release);
+        self.set_gain(self.parameters.gain);
+    }
+
+    /// Process a frame of the carrier, shaped by the spectral envelope of a frame of the
+    /// modulator.
+    pub fn tick(&mut self, carrier: &Stereo<f32>, modulator: &Stereo<f32>) -> Stereo<f32> {
+        [
+            self.gain * self.left.tick(carrier[0], modulator[0]),
+            self.gain * self.right.tick(carrier[1], modulator[1]),
+        ]
+    }
+
+    pub fn set_low(&mut self, val: f32) {
+        self.parameters.low = val;
+        self.update_bands();
+    }
+
+    pub fn set_high(&mut self, val: f32) {
+        self.parameters.high = val;
+        self.update_bands();
+    }
+
+    pub fn set_q(&mut self, val: f32) {
+        self.parameters.q = val;
+        self.update_bands();
+    }
+
+    pub fn set_attack(&mut self, val: f32) {
+        self.parameters.attack = val;
+        for channel in [&mut self.left, &mut self.right] {
+            for envelope in channel.envelopes.iter_mut() {
+                envelope.set_attack(val, self.sample_rate);
+            }
+        }
+    }
+
+    pub fn set_release(&mut self, val: f32) {
+        self.parameters.release = val;
+        for channel in [&mut self.left, &mut self.right] {
+            for envelope in channel.envelopes.iter_mut() {
+                envelope.set_release(val, self.sample_rate);
+            }
+        }
+    }
+
+    pub fn set_formant_shift(&mut self, val: f32) {
+        self.parameters.formant_shift = val;
+        self.update_bands();
+    }
+
+    pub fn set_gain(&mut self, val: f32) {
+        self.parameters.gain = val;
+        self.gain = db_to_gain(val);
+    }
+
+    /// Reset the vocoder by clearing the filters and envelopes.
+    pub fn reset(&mut self) -> &mut Self {
+        self.left.reset();
+        self.right.reset();
+        self
+    }
+
+    /// Configure the filter banks from the band range, quality factor and formant shift.
+    fn update_bands(&mut self) {
+        let VocoderParameters { low, high, q, .. } = self.parameters;
+        let low = low.max(1.0);
+        let high = high.max(low);
+        let shift = exp2f(self.parameters.formant_shift / 12.0);
+
+        for band in 0..BANDS {
+            let frequency = if BANDS > 1 {
+                low * powf(high / low, band as f32 / (BANDS - 1) as f32)
+            } else {
+                sqrtf(low * high)
+            };
+            for channel in [&mut self.left, &mut self.right] {
+                channel.modulator[band].set_bandpass(frequency, q, self.sample_rate);
+                channel.carrier[band].set_bandpass(shift * frequency, q, self.sample_rate);
+            }
+        }
+    }
+}
+
+impl<const BANDS: usize> VocoderChannel<BANDS> {
+    const fn const_default() -> Self {
+        Self {
+            carrier: [Biquad::const_default(); BANDS],
+            modulator: [Biquad::const_default(); BANDS],
+            envelopes: [EnvelopeFollower::const_default(); BANDS],
+        }
+    }
+
+    #[inline(always)]
+    fn tick(&mut self, carrier: f32, modulator: f32) -> f32 {
+        let mut out = 0.0;
+        for ((carrier_band, modulator_band), envelope) in self
+            .carrier
+            .iter_mut()
+            .zip(self.modulator.iter_mut())
+            .zip(self.envelopes.iter_mut())
+        {
+            let level = envelope.tick(modulator_band.tick(&modulator).abs());
+            out += level * carrier_band.tick(&carrier);
+        }
+        out
+    }
+
+    fn reset(&mut self) {
+        for ((carrier, modulator), envelope) in self
+            .carrier
+            .iter_mut()
+            .zip(self.modulator.iter_mut())
+            .zip(self.envelopes.iter_mut())
+        {
+            carrier.reset();
+            modulator.reset();
+            envelope.reset();
+        }
+    }
+}
+
+impl VocoderParameters {
+    pub const fn const_default() -> Self {
+        VocoderParameters {
+            low: 100.0,
+            high: 8_000.0,
+            q: 4.0,
+            attack: 5.0,
+            release: 50.0,
+            formant_shift: 0.0,
+            gain: 0.0,
+        }
+    }
+}
+
+impl Default for VocoderParameters {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl<const BANDS: usize> Default for Vocoder<BANDS> {
+    fn default() -> Self {
+        Self::new(VocoderParameters::const_default())
+    }
+}
+
+impl<const BANDS: usize> SidechainNode<Stereo<f32>, Stereo<f32>, Stereo<f32>> for Vocoder<BANDS> {
+    fn prepare(&mut self, sample_rate: usize) {
+        Vocoder::prepare(self, sample_rate);
+    }
+
+    fn tick_sidechain(&mut self, input: &Stereo<f32>, key: &Stereo<f32>) -> Stereo<f32> {
+        Vocoder::tick(self, input, key)
+    }
+}
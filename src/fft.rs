@@ -0,0 +1,12 @@
+mod complex;
+mod mixed_radix;
+mod radix2;
+mod real;
+mod twiddles;
+mod window;
+
+pub use complex::*;
+pub use mixed_radix::*;
+pub use radix2::*;
+pub use real::*;
+pub use window::*;
@@ -0,0 +1,125 @@
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use libm::{atan2f, sincosf, sqrtf};
+
+/// Complex number in Cartesian form.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    pub const fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// Construct a complex number from its magnitude and phase in radians.
+    #[inline(always)]
+    pub fn from_polar(magnitude: f32, phase: f32) -> Self {
+        let (sin, cos) = sincosf(phase);
+        Self::new(magnitude * cos, magnitude * sin)
+    }
+
+    #[inline(always)]
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Magnitude of the complex number.
+    #[inline(always)]
+    pub fn norm(self) -> f32 {
+        sqrtf(self.norm_sqr())
+    }
+
+    /// Squared magnitude of the complex number, i.e. the power of a bin.
+    #[inline(always)]
+    pub fn norm_sqr(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Phase of the complex number in radians, in the range `-PI..=PI`.
+    #[inline(always)]
+    pub fn arg(self) -> f32 {
+        atan2f(self.im, self.re)
+    }
+
+    #[inline(always)]
+    pub fn scale(self, factor: f32) -> Self {
+        Self::new(factor * self.re, factor * self.im)
+    }
+
+    /// Multiply by the imaginary unit.
+    #[inline(always)]
+    pub(super) fn mul_i(self) -> Self {
+        Self::new(-self.im, self.re)
+    }
+
+    /// Divide by the imaginary unit.
+    #[inline(always)]
+    pub(super) fn div_i(self) -> Self {
+        Self::new(self.im, -self.re)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl AddAssign for Complex {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Complex {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Complex {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
@@ -0,0 +1,154 @@
+use super::{Complex, twiddles};
+
+/// Mixed-radix complex FFT of `N` points, for any `N`.
+///
+/// The size is split into its prime factors, and each factor is a Stockham stage which reads from
+/// one buffer and writes to the other in sorted order. The transforms take a scratch buffer from
+/// the caller, and always leave the result in the data buffer. Sizes with only small prime
+/// factors are fast, while a large prime factor `p` costs `p` operations per point of its stage.
+///
+/// Like [`super::Fft`], the twiddle factors are computed at compile-time and the inverse
+/// transform is scaled by `1 / N`.
+///
+/// ```
+/// use dspkit::fft::{Complex, MixedRadixFft};
+///
+/// static FFT: MixedRadixFft<12> = MixedRadixFft::new();
+///
+/// let mut data = [Complex::new(1.0, 0.0); 12];
+/// let mut scratch = [Complex::ZERO; 12];
+/// FFT.forward(&mut data, &mut scratch);
+///
+/// // A constant signal only has a DC component.
+/// assert!((data[0].re - 12.0).abs() < 1e-5);
+/// assert!(data[1..].iter().all(|bin| bin.norm() < 1e-5));
+/// ```
+#[derive(Clone)]
+pub struct MixedRadixFft<const N: usize> {
+    /// Twiddle factors `e^(-2 pi i k / N)`.
+    twiddles: [Complex; N],
+}
+
+impl<const N: usize> MixedRadixFft<N> {
+    /// Construct the FFT, computing the twiddle factors. Can be evaluated at compile-time.
+    pub const fn new() -> Self {
+        const {
+            assert!(N >= 1, "FFT size must be at least 1");
+        }
+
+        Self {
+            twiddles: twiddles::table(N),
+        }
+    }
+
+    /// Transform the samples in place into their spectrum.
+    ///
+    /// ```
+    /// use dspkit::fft::{Complex, MixedRadixFft};
+    ///
+    /// // A size with factors 3 and 5 matches a direct evaluation of the DFT.
+    /// let fft = MixedRadixFft::<15>::new();
+    /// let input: [Complex; 15] = core::array::from_fn(|n| {
+    ///     Complex::new(libm::sinf(n as f32), libm::cosf(0.3 * (n * n) as f32))
+    /// });
+    ///
+    /// let mut data = input;
+    /// let mut scratch = [Complex::ZERO; 15];
+    /// fft.forward(&mut data, &mut scratch);
+    ///
+    /// for (k, bin) in data.iter().enumerate() {
+    ///     let expected = input.iter().enumerate().fold(Complex::ZERO, |acc, (n, x)| {
+    ///         let phase = -2.0 * core::f32::consts::PI * ((k * n) % 15) as f32 / 15.0;
+    ///         acc + *x * Complex::from_polar(1.0, phase)
+    ///     });
+    ///     assert!((*bin - expected).norm() < 1e-4);
+    /// }
+    ///
+    /// // The inverse restores the input.
+    /// fft.inverse(&mut data, &mut scratch);
+    /// for (x, y) in input.iter().zip(data.iter()) {
+    ///     assert!((*x - *y).norm() < 1e-5);
+    /// }
+    /// ```
+    pub fn forward(&self, data: &mut [Complex; N], scratch: &mut [Complex; N]) {
+        self.transform(data, scratch, false);
+    }
+
+    /// Transform the spectrum in place back into samples, scaled by `1 / N`.
+    pub fn inverse(&self, data: &mut [Complex; N], scratch: &mut [Complex; N]) {
+        self.transform(data, scratch, true);
+        let scale = 1.0 / N as f32;
+        for value in data.iter_mut() {
+            *value = value.scale(scale);
+        }
+    }
+
+    fn transform(&self, data: &mut [Complex; N], scratch: &mut [Complex; N], inverse: bool) {
+        let mut length = N;
+        let mut stride = 1;
+        let mut in_data = true;
+
+        while length > 1 {
+            let radix = smallest_factor(length);
+            if in_data {
+                self.stage(data, scratch, length, stride, radix, inverse);
+            } else {
+                self.stage(scratch, data, length, stride, radix, inverse);
+            }
+            in_data = !in_data;
+            length /= radix;
+            stride *= radix;
+        }
+
+        if !in_data {
+            data.copy_from_slice(scratch);
+        }
+    }
+
+    /// Decimation-in-frequency stage of `radix`, splitting sub-transforms of `length` points
+    /// interleaved by `stride`.
+    fn stage(
+        &self,
+        input: &[Complex; N],
+        output: &mut [Complex; N],
+        length: usize,
+        stride: usize,
+        radix: usize,
+        inverse: bool,
+    ) {
+        let twiddle = |k: usize| {
+            let twiddle = self.twiddles[k % N];
+            if inverse { twiddle.conj() } else { twiddle }
+        };
+        let span = length / radix;
+        let root = N / radix;
+        let step = N / length;
+
+        for j in 0..span {
+            for u in 0..radix {
+                let rotation = twiddle(j * u * step);
+                for q in 0..stride {
+                    let mut sum = Complex::ZERO;
+                    for r in 0..radix {
+                        sum += input[q + stride * (j + span * r)] * twiddle((r * u) % radix * root);
+                    }
+                    output[q + stride * (radix * j + u)] = sum * rotation;
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for MixedRadixFft<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Smallest prime factor of `n`, which is at least 2.
+fn smallest_factor(n: usize) -> usize {
+    (2..)
+        .take_while(|d| d * d <= n)
+        .find(|d| n.is_multiple_of(*d))
+        .unwrap_or(n)
+}
@@ -0,0 +1,103 @@
+use super::{Complex, twiddles};
+
+/// Radix-2 complex FFT of `N` points, with `N` a power of two.
+///
+/// The transforms run in place, reordering the input by bit reversal before the butterflies. The
+/// twiddle factors are computed by [`Fft::new`] at compile-time, so an FFT can be held in a
+/// `static`. The inverse transform is scaled by `1 / N`, so the round trip is the identity.
+///
+/// ```
+/// use dspkit::fft::{Complex, Fft};
+///
+/// static FFT: Fft<8> = Fft::new();
+///
+/// let mut data = [Complex::ZERO; 8];
+/// data[1] = Complex::new(1.0, 0.0);
+/// FFT.forward(&mut data);
+///
+/// // A delayed impulse has a flat magnitude spectrum.
+/// assert!(data.iter().all(|bin| (bin.norm() - 1.0).abs() < 1e-6));
+///
+/// FFT.inverse(&mut data);
+/// assert!((data[1].re - 1.0).abs() < 1e-6 && data[0].norm() < 1e-6);
+/// ```
+#[derive(Clone)]
+pub struct Fft<const N: usize> {
+    /// Twiddle factors `e^(-2 pi i k / N)`, of which the first half are used.
+    twiddles: [Complex; N],
+}
+
+impl<const N: usize> Fft<N> {
+    /// Construct the FFT, computing the twiddle factors. Can be evaluated at compile-time.
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                N.is_power_of_two(),
+                "radix-2 FFT size must be a power of two"
+            );
+        }
+
+        Self {
+            twiddles: twiddles::table(N),
+        }
+    }
+
+    /// Transform the samples in place into their spectrum.
+    pub fn forward(&self, data: &mut [Complex; N]) {
+        transform(data, &self.twiddles, 1, false);
+    }
+
+    /// Transform the spectrum in place back into samples, scaled by `1 / N`.
+    pub fn inverse(&self, data: &mut [Complex; N]) {
+        transform(data, &self.twiddles, 1, true);
+        let scale = 1.0 / N as f32;
+        for value in data.iter_mut() {
+            *value = value.scale(scale);
+        }
+    }
+}
+
+impl<const N: usize> Default for Fft<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// In-place radix-2 decimation-in-time transform of `N` points. The twiddle factor of index `k`
+/// must be `e^(-2 pi i k / (step * N))`, so a table with a finer period can be shared. The inverse
+/// transform is not scaled.
+pub(super) fn transform<const N: usize>(
+    data: &mut [Complex; N],
+    twiddles: &[Complex],
+    step: usize,
+    inverse: bool,
+) {
+    if N < 2 {
+        return;
+    }
+
+    let bits = N.trailing_zeros();
+    for i in 0..N {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= N {
+        let half = size / 2;
+        let stride = step * N / size;
+        for start in (0..N).step_by(size) {
+            for j in 0..half {
+                let twiddle = twiddles[j * stride];
+                let twiddle = if inverse { twiddle.conj() } else { twiddle };
+                let odd = data[start + j + half] * twiddle;
+                let even = data[start + j];
+                data[start + j] = even + odd;
+                data[start + j + half] = even - odd;
+            }
+        }
+        size *= 2;
+    }
+}
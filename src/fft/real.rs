@@ -0,0 +1,94 @@
+use super::{Complex, radix2, twiddles};
+
+/// FFT of `2 * N` real samples, with `N` a power of two.
+///
+/// The samples are packed into `N` complex values of consecutive pairs, i.e. `data[n]` holds the
+/// samples `2 * n` and `2 * n + 1` in its real and imaginary parts. The packed samples are
+/// transformed by a complex FFT of `N` points, and then separated into the spectrum of the real
+/// signal, which costs about half of a complex FFT of `2 * N` points.
+///
+/// The spectrum of a real signal is symmetric, so only the bins `0..N` are kept. The DC and
+/// Nyquist bins are both real, and the Nyquist bin is packed into the imaginary part of the DC
+/// bin. The inverse transform is scaled by `1 / (2 * N)`, so the round trip is the identity.
+///
+/// ```
+/// use dspkit::fft::{Complex, RealFft};
+///
+/// static FFT: RealFft<4> = RealFft::new();
+///
+/// // The samples [1, -1, 1, -1, 1, -1, 1, -1] only have a Nyquist component.
+/// let mut data = [Complex::new(1.0, -1.0); 4];
+/// FFT.forward(&mut data);
+/// assert!(data[0].re.abs() < 1e-6 && (data[0].im - 8.0).abs() < 1e-6);
+/// assert!(data[1..].iter().all(|bin| bin.norm() < 1e-6));
+///
+/// FFT.inverse(&mut data);
+/// assert!(data.iter().all(|pair| (pair.re - 1.0).abs() < 1e-6 && (pair.im + 1.0).abs() < 1e-6));
+/// ```
+#[derive(Clone)]
+pub struct RealFft<const N: usize> {
+    /// Twiddle factors `e^(-pi i k / N)`, i.e. of a transform of `2 * N` points. The even entries
+    /// are the twiddle factors of the complex FFT of `N` points.
+    twiddles: [Complex; N],
+}
+
+impl<const N: usize> RealFft<N> {
+    /// Construct the FFT, computing the twiddle factors. Can be evaluated at compile-time.
+    pub const fn new() -> Self {
+        const {
+            assert!(N.is_power_of_two(), "real FFT size must be a power of two");
+        }
+
+        Self {
+            twiddles: twiddles::table(2 * N),
+        }
+    }
+
+    /// Transform the packed samples in place into the packed spectrum.
+    pub fn forward(&self, data: &mut [Complex; N]) {
+        radix2::transform(data, &self.twiddles, 2, false);
+
+        let dc = data[0];
+        data[0] = Complex::new(dc.re + dc.im, dc.re - dc.im);
+        for k in 1..=N / 2 {
+            let (a, b) = (data[k], data[N - k]);
+            let even = (a + b.conj()).scale(0.5);
+            let odd = (a - b.conj()).scale(0.5).div_i();
+            data[k] = even + self.twiddles[k] * odd;
+            if k != N - k {
+                let even = (b + a.conj()).scale(0.5);
+                let odd = (b - a.conj()).scale(0.5).div_i();
+                data[N - k] = even + self.twiddles[N - k] * odd;
+            }
+        }
+    }
+
+    /// Transform the packed spectrum in place back into packed samples, scaled by `1 / (2 * N)`.
+    pub fn inverse(&self, data: &mut [Complex; N]) {
+        let packed = data[0];
+        data[0] = Complex::new(packed.re + packed.im, packed.re - packed.im).scale(0.5);
+        for k in 1..=N / 2 {
+            let (a, b) = (data[k], data[N - k]);
+            let even = (a + b.conj()).scale(0.5);
+            let odd = ((a - b.conj()) * self.twiddles[k].conj()).scale(0.5);
+            data[k] = even + odd.mul_i();
+            if k != N - k {
+                let even = (b + a.conj()).scale(0.5);
+                let odd = ((b - a.conj()) * self.twiddles[N - k].conj()).scale(0.5);
+                data[N - k] = even + odd.mul_i();
+            }
+        }
+
+        radix2::transform(data, &self.twiddles, 2, true);
+        let scale = 1.0 / N as f32;
+        for value in data.iter_mut() {
+            *value = value.scale(scale);
+        }
+    }
+}
+
+impl<const N: usize> Default for RealFft<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
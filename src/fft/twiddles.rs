@@ -0,0 +1,57 @@
+use super::Complex;
+
+use core::f64::consts::FRAC_PI_2;
+
+/// Number of terms of the Taylor series of the sine and cosine. Within a quadrant, the error of
+/// the truncated series is far below the precision of `f32`.
+const SERIES_TERMS: usize = 12;
+
+/// Table of the twiddle factors `e^(-2 pi i k / period)` for `k` in `0..N`, computed at
+/// compile-time.
+pub(super) const fn table<const N: usize>(period: usize) -> [Complex; N] {
+    let mut table = [Complex::ZERO; N];
+    let mut k = 0;
+    while k < N {
+        table[k] = twiddle(k, period);
+        k += 1;
+    }
+    table
+}
+
+/// The twiddle factor `e^(-2 pi i k / n)`.
+///
+/// The angle is reduced to a quadrant with exact integer arithmetic, so the table is as accurate
+/// for large sizes as for small ones.
+const fn twiddle(k: usize, n: usize) -> Complex {
+    let k = k % n;
+    let quadrant = 4 * k / n;
+    let remainder = 4 * k - quadrant * n;
+    let (sin, cos) = sin_cos(FRAC_PI_2 * remainder as f64 / n as f64);
+
+    let (cos, sin) = match quadrant {
+        0 => (cos, sin),
+        1 => (-sin, cos),
+        2 => (-cos, -sin),
+        _ => (sin, -cos),
+    };
+    Complex::new(cos as f32, -sin as f32)
+}
+
+/// Sine and cosine of an angle within the first quadrant, from their Taylor series.
+const fn sin_cos(x: f64) -> (f64, f64) {
+    let x2 = x * x;
+    let mut sin = 0.0;
+    let mut cos = 0.0;
+    let mut sin_term = x;
+    let mut cos_term = 1.0;
+    let mut n = 0;
+    while n < SERIES_TERMS {
+        sin += sin_term;
+        cos += cos_term;
+        let m = (2 * n + 1) as f64;
+        sin_term *= -x2 / ((m + 1.0) * (m + 2.0));
+        cos_term *= -x2 / (m * (m + 1.0));
+        n += 1;
+    }
+    (sin, cos)
+}
@@ -1,13 +1,16 @@
 #![no_std]
 
 pub mod components;
+mod decibel;
 pub mod effects;
+pub mod fft;
 mod frame;
 mod parameter;
 mod pcm;
 
+pub use decibel::{MIN_DB, db_to_gain, db_to_power, gain_to_db, power_to_db};
 pub use frame::{Frame, Mono, Stereo, ToMono};
-pub use pcm::PCM;
+pub use pcm::{I8, I16, I32, OutOfRange, PCM};
 
 /// An audio node which can process individual or batches of samples.
 pub trait AudioNode<I, O> {
@@ -24,4 +27,29 @@ pub trait AudioNode<I, O> {
             output[idx] = self.tick(val);
         }
     }
+
+    /// Delay in samples between the input and output signals.
+    fn latency(&self) -> usize {
+        0
+    }
+}
+
+/// An audio node with an additional key input, e.g. the sidechain of a dynamics processor.
+///
+/// The key signal only drives the processing (such as the level detector) and is not heard in the
+/// output.
+pub trait SidechainNode<I, K, O> {
+    /// Prepare the audio node before processing.
+    #[allow(unused_variables)]
+    fn prepare(&mut self, sample_rate: usize) {}
+
+    /// Process a single sample, using the key sample to drive the processing.
+    fn tick_sidechain(&mut self, input: &I, key: &K) -> O;
+
+    /// Process a batch of samples, using the batch of key samples to drive the processing.
+    fn batch_sidechain(&mut self, input: &[I], key: &[K], output: &mut [O]) {
+        for (idx, (val, key)) in input.iter().zip(key).enumerate() {
+            output[idx] = self.tick_sidechain(val, key);
+        }
+    }
 }
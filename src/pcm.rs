@@ -1,3 +1,8 @@
+use libm::{floorf, round, roundf, tanhf};
+
+/// Level above which [`OutOfRange::Saturate`] starts to bend the signal towards the limits.
+const SATURATION_KNEE: f32 = 0.8;
+
 /// PCM audio encoding representation.
 pub trait PCM: Copy + Clone + PartialOrd + From<f32> + Into<f32> {
     /// Represents the lowest possible PCM value.
@@ -9,6 +14,10 @@ pub trait PCM: Copy + Clone + PartialOrd + From<f32> + Into<f32> {
     /// Represents a "silent" signal for the audio encoding.
     const PCM_EQUILIBRIUM: Self;
 
+    /// Size of a quantization step of the encoding, relative to the range `-1.0..=1.0`. Zero for
+    /// encodings which are not quantized.
+    const PCM_STEP: f32 = 0.0;
+
     /// Clamp PCM signal within the valid range.
     fn constrain(self) -> Self {
         if self < Self::PCM_LOW {
@@ -19,10 +28,155 @@ pub trait PCM: Copy + Clone + PartialOrd + From<f32> + Into<f32> {
             self
         }
     }
+
+    /// Convert a sample in the range `-1.0..=1.0` into this encoding, applying the policy to
+    /// samples outside of the range.
+    ///
+    /// ```
+    /// use dspkit::{I16, OutOfRange, PCM};
+    ///
+    /// assert_eq!(I16::from_f32_with(1.5, OutOfRange::Clamp), I16(i16::MAX));
+    /// assert_eq!(I16::from_f32_with(1.5, OutOfRange::Wrap), I16(-16_384));
+    /// assert_eq!(f32::from_f32_with(-1.5, OutOfRange::Wrap), 0.5);
+    /// ```
+    #[inline(always)]
+    fn from_f32_with(value: f32, policy: OutOfRange) -> Self {
+        Self::from(policy.apply(value))
+    }
+}
+
+/// Policy for samples outside of the range `-1.0..=1.0` when converting into a [`PCM`] encoding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutOfRange {
+    /// Clamp to the limits of the range.
+    Clamp,
+    /// Bend the signal smoothly towards the limits of the range. Samples above the knee of 0.8 are
+    /// also bent, even if they are within the range.
+    Saturate,
+    /// Wrap around to the opposite limit, like the overflow of an integer.
+    Wrap,
+    /// Panic in debug builds, and clamp in release builds.
+    Panic,
+}
+
+impl OutOfRange {
+    /// Apply the policy to a sample, returning a sample within the range `-1.0..=1.0`.
+    ///
+    /// ```
+    /// use dspkit::OutOfRange;
+    ///
+    /// assert_eq!(OutOfRange::Clamp.apply(1.5), 1.0);
+    /// assert_eq!(OutOfRange::Wrap.apply(1.5), -0.5);
+    /// assert_eq!(OutOfRange::Saturate.apply(0.5), 0.5);
+    /// assert!(OutOfRange::Saturate.apply(10.0) <= 1.0);
+    /// ```
+    #[inline(always)]
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            Self::Clamp => value.clamp(-1.0, 1.0),
+            Self::Saturate => {
+                let magnitude = value.abs();
+                if magnitude <= SATURATION_KNEE {
+                    value
+                } else {
+                    // Continue the slope of the identity at the knee, approaching the limit.
+                    let headroom = 1.0 - SATURATION_KNEE;
+                    let bent = SATURATION_KNEE
+                        + headroom * tanhf((magnitude - SATURATION_KNEE) / headroom);
+                    bent.copysign(value)
+                }
+            }
+            Self::Wrap => {
+                if (-1.0..=1.0).contains(&value) {
+                    value
+                } else {
+                    let shifted = value + 1.0;
+                    shifted - 2.0 * floorf(0.5 * shifted) - 1.0
+                }
+            }
+            Self::Panic => {
+                debug_assert!(
+                    (-1.0..=1.0).contains(&value),
+                    "sample {value} is outside of the range -1.0..=1.0"
+                );
+                value.clamp(-1.0, 1.0)
+            }
+        }
+    }
 }
 
 impl PCM for f32 {
     const PCM_LOW: Self = -1.0;
-    const PCM_HIGH: Self = -1.0;
+    const PCM_HIGH: Self = 1.0;
     const PCM_EQUILIBRIUM: Self = 0.0;
 }
+
+/// Implement a signed integer PCM encoding. Conversions from `f32` scale by the magnitude of the
+/// lowest value, and clamp to the limits of the integer.
+macro_rules! integer_pcm {
+    ($(#[$doc:meta])* $name:ident, $int:ty, $wide:ty, $round:path) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct $name(pub $int);
+
+        impl $name {
+            /// Scale between a sample in the range `-1.0..=1.0` and the integer.
+            const SCALE: $wide = -(<$int>::MIN as $wide);
+        }
+
+        impl PCM for $name {
+            const PCM_LOW: Self = Self(<$int>::MIN);
+            const PCM_HIGH: Self = Self(<$int>::MAX);
+            const PCM_EQUILIBRIUM: Self = Self(0);
+            const PCM_STEP: f32 = (1.0 / Self::SCALE) as f32;
+        }
+
+        impl From<f32> for $name {
+            #[inline(always)]
+            fn from(value: f32) -> Self {
+                let scaled = $round(value as $wide * Self::SCALE);
+                Self(scaled.clamp(<$int>::MIN as $wide, <$int>::MAX as $wide) as $int)
+            }
+        }
+
+        impl From<$name> for f32 {
+            #[inline(always)]
+            fn from(value: $name) -> Self {
+                (value.0 as $wide / $name::SCALE) as f32
+            }
+        }
+    };
+}
+
+integer_pcm!(
+    /// 8-bit signed integer PCM encoding.
+    I8,
+    i8,
+    f32,
+    roundf
+);
+
+integer_pcm!(
+    /// 16-bit signed integer PCM encoding.
+    ///
+    /// ```
+    /// use dspkit::I16;
+    ///
+    /// assert_eq!(I16::from(0.5), I16(16_384));
+    /// assert_eq!(I16::from(2.0), I16(i16::MAX));
+    /// assert_eq!(f32::from(I16(-32_768)), -1.0);
+    /// ```
+    I16,
+    i16,
+    f32,
+    roundf
+);
+
+integer_pcm!(
+    /// 32-bit signed integer PCM encoding. Conversions are computed in double precision, since
+    /// `f32` cannot represent every 32-bit integer.
+    I32,
+    i32,
+    f64,
+    round
+);